@@ -0,0 +1,22 @@
+// Exports reference `svg` and `png` images from javascript jdenticon into
+// `test_pics`, for tests in `src/jdenticon.rs`.
+//
+// Public keys are passed in hexadecimal format without `0x`, as polkadot.js
+// does for its `substrate` icon theme.
+//
+//     npm install && npm run export
+const fs = require('fs');
+const path = require('path');
+const jdenticon = require('jdenticon');
+
+const KEYS = [
+  ['alice', 'd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d', 64],
+  ['bob', '8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48', 30],
+];
+
+const dir = path.join(__dirname, '..', '..', 'test_pics');
+for (const [name, hash, size] of KEYS) {
+  const file = path.join(dir, `${name}_jdenticon_${size}`);
+  fs.writeFileSync(`${file}.svg`, jdenticon.toSvg(hash, size));
+  fs.writeFileSync(`${file}.png`, jdenticon.toPng(hash, size));
+}
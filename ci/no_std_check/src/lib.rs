@@ -0,0 +1,30 @@
+//! Calls `plot_icon` rasterizer without `alloc`, so that CI checks it
+//! is built and linked for the targets without allocator.
+#![no_std]
+
+use core::panic::PanicInfo;
+
+use plot_icon::{colors::get_colors, write_rgba, write_rgba_antialiased, write_rgba_with_colors};
+
+const SIZE: u16 = 16;
+const STRIDE: usize = SIZE as usize * 4;
+
+/// Draw identicons for the input into stack buffer, returns number of
+/// errors
+#[no_mangle]
+pub extern "C" fn draw_identicons(seed: u32) -> u8 {
+    let into_id = &seed.to_le_bytes();
+    let mut buffer = [0u8; STRIDE * SIZE as usize];
+    let results = [
+        write_rgba(into_id, SIZE, &mut buffer, STRIDE),
+        write_rgba_with_colors(get_colors(into_id), SIZE, &mut buffer, STRIDE),
+        write_rgba_antialiased(into_id, SIZE, &mut buffer, STRIDE),
+    ];
+    core::hint::black_box(&buffer);
+    results.iter().filter(|result| result.is_err()).count() as u8
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
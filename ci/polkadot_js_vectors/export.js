@@ -0,0 +1,103 @@
+// Exports colors of polkadot.js identicons into
+// `test_pics/polkadot_js_vectors.txt`, for test `conformance_vectors` in
+// `src/colors.rs`.
+//
+// Circle colors are taken from `polkadotIcon` of `@polkadot/ui-shared`, and
+// the `hsl()` colors are resolved into RGB by node-canvas, same as the
+// canvas `fillStyle` does it. Package does not export coloring scheme and
+// rotation, these are calculated here from the same hash, and the colors
+// they give are checked against the package colors.
+//
+//     npm install && npm run export
+import { createHash } from 'crypto';
+import fs from 'fs';
+import path from 'path';
+import { fileURLToPath } from 'url';
+
+import canvas from 'canvas';
+import { polkadotIcon } from '@polkadot/ui-shared';
+import { u8aToHex } from '@polkadot/util';
+import { blake2AsU8a, cryptoWaitReady } from '@polkadot/util-crypto';
+
+// same order and frequencies as in `@polkadot/ui-shared` icons/polkadot
+const SCHEMES = [
+  { name: 'target', freq: 1, colors: [0, 28, 0, 0, 28, 0, 0, 28, 0, 0, 28, 0, 0, 28, 0, 0, 28, 0, 1] },
+  { name: 'cube', freq: 20, colors: [0, 1, 3, 2, 4, 3, 0, 1, 3, 2, 4, 3, 0, 1, 3, 2, 4, 3, 5] },
+  { name: 'quazar', freq: 16, colors: [1, 2, 3, 1, 2, 4, 5, 5, 4, 1, 2, 3, 1, 2, 4, 5, 5, 4, 0] },
+  { name: 'flower', freq: 32, colors: [0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 3] },
+  { name: 'cyclic', freq: 32, colors: [0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6] },
+  { name: 'vmirror', freq: 128, colors: [0, 1, 2, 3, 4, 5, 3, 4, 2, 0, 1, 6, 7, 8, 9, 7, 8, 6, 10] },
+  { name: 'hmirror', freq: 128, colors: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 8, 6, 7, 5, 3, 4, 2, 11] }
+];
+
+await cryptoWaitReady();
+
+const ZERO = blake2AsU8a(new Uint8Array(32));
+
+// scheme, rotation and palette indices of the circle colors
+function getScheme (pubKey) {
+  const total = SCHEMES.map((s) => s.freq).reduce((a, b) => a + b);
+  const id = blake2AsU8a(pubKey).map((x, i) => (x + 256 - ZERO[i]) % 256);
+  const d = Math.floor((id[30] + id[31] * 256) % total);
+  const rot = (id[28] % 6) * 3;
+  let cum = 0;
+  const scheme = SCHEMES.find((s) => { cum += s.freq; return d < cum; });
+  const indices = scheme.colors.map((_, i) => scheme.colors[i < 18 ? (i + rot) % 18 : 18]);
+  return { id, indices, rot, scheme: scheme.name };
+}
+
+// palette color with given index, as `@polkadot/ui-shared` sets it
+function paletteColor (id, index) {
+  const sat = (Math.floor(id[29] * 70 / 256 + 26) % 80) + 30;
+  const b = (id[index] + (index % 28 * 58)) % 256;
+  if (b === 0) return '#444';
+  if (b === 255) return 'transparent';
+  const h = Math.floor(b % 64 * 360 / 64);
+  const l = [53, 15, 35, 75][Math.floor(b / 64)];
+  return `hsl(${h}, ${sat}%, ${l}%)`;
+}
+
+const context = canvas.createCanvas(1, 1).getContext('2d');
+
+// CSS color resolved by canvas, as `rrggbb` or `transparent`
+function resolve (css) {
+  context.fillStyle = '#000000';
+  context.fillStyle = css;
+  const resolved = context.fillStyle;
+  if (resolved.startsWith('#')) return resolved.slice(1);
+  if (/^rgba\(.*,\s*0\)$/.test(resolved)) return 'transparent';
+  throw new Error(`unexpected color ${css} resolved as ${resolved}`);
+}
+
+const keys = [
+  'd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d', // Alice
+  '8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48' // Bob
+].map((k) => Buffer.from(k, 'hex'));
+for (let i = 0; keys.length < 4096; i++) {
+  const index = Buffer.alloc(4);
+  index.writeUInt32LE(i);
+  keys.push(createHash('sha256').update(index).digest());
+}
+
+const lines = [
+  '# Colors of polkadot.js identicons, for conformance tests',
+  '#',
+  '# Line format: public key, coloring scheme, rotation, then colors of 19',
+  '# circles, numbered as in `coloring_order`, as rrggbb or transparent.',
+  '# First two keys are Alice and Bob, others are sha256 of 4-byte',
+  '# little-endian index. Generated by `ci/polkadot_js_vectors`: colors are',
+  '# from `polkadotIcon` of `@polkadot/ui-shared`, resolved by node-canvas.'
+];
+for (const key of keys) {
+  const circles = polkadotIcon(u8aToHex(key), { isAlternative: false }).slice(1);
+  const { id, indices, rot, scheme } = getScheme(key);
+  indices.forEach((index, i) => {
+    if (paletteColor(id, index) !== circles[i].fill) {
+      throw new Error(`${u8aToHex(key)}: circle ${i} is ${circles[i].fill}, scheme gives ${paletteColor(id, index)}`);
+    }
+  });
+  lines.push([key.toString('hex'), scheme, rot, ...circles.map((c) => resolve(c.fill))].join(' '));
+}
+
+const dir = path.join(path.dirname(fileURLToPath(import.meta.url)), '..', '..', 'test_pics');
+fs.writeFileSync(path.join(dir, 'polkadot_js_vectors.txt'), lines.join('\n') + '\n');
@@ -0,0 +1,857 @@
+//! Network badge, small mark in the corner of the identicon
+//!
+//! Same public key is displayed with the same identicon on all networks.
+//! Badge shows the network: it is a colored disc with optional glyph, or a
+//! user-supplied logo, placed on the outer circle of the identicon, towards
+//! the selected image corner. Badge is set in the [`Style`](crate::Style)
+//! and is drawn by all generators accepting the style.
+//!
+//! Networks with known SS58 prefix get badge color from the built-in table,
+//! see [`Badge::for_network`].
+#[cfg(all(feature = "vec", feature = "pix"))]
+use alloc::format;
+use alloc::{string::String, vec, vec::Vec};
+#[cfg(feature = "vec")]
+use svg::node::{element, Text};
+
+use crate::colors::Color;
+use crate::error::IdenticonError;
+use crate::scene::{Element, Scene};
+
+/// Networks with known colors: SS58 prefix, network name and color
+///
+/// This is a seed list of a few networks, not the complete SS58 registry.
+/// Badges for other networks get grey color, set [`Badge::color`] directly
+/// for them.
+#[rustfmt::skip]
+pub const NETWORK_COLORS: &[(u16, &str, Color)] = &[
+    (0, "polkadot", Color{red: 230, green: 0, blue: 122, alpha: 255}),
+    (2, "kusama", Color{red: 0, green: 0, blue: 0, alpha: 255}),
+    (1284, "moonbeam", Color{red: 83, green: 203, blue: 201, alpha: 255}),
+    (1285, "moonriver", Color{red: 242, green: 183, blue: 5, alpha: 255}),
+];
+
+/// Badge color for networks not in the table
+const DEFAULT_COLOR: Color = Color {
+    red: 128,
+    green: 128,
+    blue: 128,
+    alpha: 255,
+};
+
+/// Glyph height, in parts of the badge diameter
+const GLYPH_HEIGHT: f32 = 0.5;
+
+/// Built-in 5x7 pixel font: digits, then latin capital letters;
+/// each row is 5 bits, the most significant one on the left
+#[rustfmt::skip]
+const FONT: [[u8; 7]; 36] = [
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E], // 0
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E], // 1
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F], // 2
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E], // 3
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02], // 4
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E], // 5
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E], // 6
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // 7
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E], // 8
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C], // 9
+    [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11], // A
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // B
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // C
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C], // D
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // E
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // F
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // G
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // H
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // L
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // N
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // O
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // P
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // Q
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // R
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // S
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // W
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // X
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // Y
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // Z
+];
+
+/// Image corner the badge is placed towards
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// What is drawn in the badge
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mark {
+    /// Plain disc of the badge color
+    #[default]
+    Disc,
+    /// Disc with a glyph from the built-in pixel font over it
+    ///
+    /// Digits and latin letters are supported, lowercase letters are drawn
+    /// as capital ones, other characters are not drawn. Glyph is black or
+    /// white, whichever is more visible on the badge color.
+    Glyph(char),
+    /// User logo as RGBA8 pixels, stretched to fill the badge square
+    /// instead of the disc
+    ///
+    /// Logo is scaled with `pix` feature, without it `png` and RGBA
+    /// generators return [`IdenticonError::UnsupportedLogo`]. In `svg`
+    /// images logo is embedded as `png` data URI with `pix` feature, and
+    /// plain disc is drawn without it.
+    Rgba(RgbaLogo),
+    /// User logo as `svg` markup, scaled to fit the badge square instead of
+    /// the disc
+    ///
+    /// `png` and RGBA generators could not draw it and return
+    /// [`IdenticonError::UnsupportedLogo`].
+    Svg(SvgLogo),
+}
+
+/// User logo as RGBA8 pixels, with pixel data length matching the size
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{badge::RgbaLogo, IdenticonError};
+///
+/// let blue = RgbaLogo::new(1, 1, vec![0, 0, 255, 255]).unwrap();
+/// assert_eq!(blue.data(), &[0, 0, 255, 255]);
+/// assert!(matches!(
+///     RgbaLogo::new(2, 2, vec![0; 4]),
+///     Err(IdenticonError::InvalidLogo)
+/// ));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RgbaLogoData"))]
+pub struct RgbaLogo {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+/// Unchecked RGBA logo, for deserialization
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RgbaLogoData {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RgbaLogoData> for RgbaLogo {
+    type Error = IdenticonError;
+    fn try_from(logo: RgbaLogoData) -> Result<Self, Self::Error> {
+        RgbaLogo::new(logo.width, logo.height, logo.data)
+    }
+}
+
+impl RgbaLogo {
+    /// RGBA logo from its size and pixel data, row by row, 4 bytes per
+    /// pixel; error [`IdenticonError::InvalidLogo`] if the logo is empty,
+    /// or the data length does not match the size
+    pub fn new(width: u32, height: u32, data: Vec<u8>) -> Result<Self, IdenticonError> {
+        let length = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(4));
+        if width == 0 || height == 0 || length != Some(data.len()) {
+            return Err(IdenticonError::InvalidLogo);
+        }
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    /// Logo width in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Logo height in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Logo pixel data
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// User logo as `svg` markup of single `svg` element
+///
+/// Markup is trusted input: it is inserted into `svg` images as is, only
+/// checked to be a single `svg` element with balanced tags, with no XML
+/// declaration or text around it. Scripts, styles and external references
+/// are not removed, only use logos from trusted sources.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{badge::SvgLogo, IdenticonError};
+///
+/// let logo = SvgLogo::new("<svg viewBox=\"0 0 2 2\"><circle cx=\"1\" cy=\"1\" r=\"1\"/></svg>").unwrap();
+/// assert!(logo.as_str().starts_with("<svg"));
+/// assert!(matches!(SvgLogo::new("<svg/><svg/>"), Err(IdenticonError::InvalidLogo)));
+/// assert!(matches!(SvgLogo::new("<g></g>"), Err(IdenticonError::InvalidLogo)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct SvgLogo(String);
+
+impl SvgLogo {
+    /// `svg` logo from the markup; error [`IdenticonError::InvalidLogo`] if
+    /// the markup is not a single `svg` element
+    pub fn new(markup: &str) -> Result<Self, IdenticonError> {
+        let markup = markup.trim();
+        if is_single_svg_element(markup) {
+            Ok(Self(String::from(markup)))
+        } else {
+            Err(IdenticonError::InvalidLogo)
+        }
+    }
+
+    /// Logo markup
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for SvgLogo {
+    type Error = IdenticonError;
+    fn try_from(markup: String) -> Result<Self, Self::Error> {
+        SvgLogo::new(&markup)
+    }
+}
+
+impl From<SvgLogo> for String {
+    fn from(logo: SvgLogo) -> Self {
+        logo.0
+    }
+}
+
+/// Check that the markup is a single `svg` element: starts with `svg` tag,
+/// and ends where this tag is closed
+///
+/// Tags are only counted, and not parsed further; comments and `CDATA`
+/// sections within the element are skipped.
+fn is_single_svg_element(markup: &str) -> bool {
+    let bytes = markup.as_bytes();
+    if !markup.starts_with("<svg")
+        || !matches!(
+            bytes.get(4),
+            Some(b' ' | b'\t' | b'\n' | b'\r' | b'>' | b'/')
+        )
+    {
+        return false;
+    }
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+        let tail = &markup[i..];
+        let skipped = [("<!--", "-->"), ("<![CDATA[", "]]>")]
+            .iter()
+            .find(|(start, _)| tail.starts_with(start))
+            .map(|(start, end)| {
+                tail[start.len()..]
+                    .find(end)
+                    .map(|a| start.len() + a + end.len())
+            });
+        match skipped {
+            Some(Some(length)) => {
+                i += length;
+                continue;
+            }
+            Some(None) => return false,
+            None => {}
+        }
+        // declarations and processing instructions
+        if tail.starts_with("<!") || tail.starts_with("<?") {
+            return false;
+        }
+        // tag end, `>` within quoted attribute values does not count
+        let mut j = i + 1;
+        let mut quote = None;
+        while j < bytes.len() {
+            match (quote, bytes[j]) {
+                (None, b'"' | b'\'') => quote = Some(bytes[j]),
+                (Some(q), a) if a == q => quote = None,
+                (None, b'>') => break,
+                _ => {}
+            }
+            j += 1;
+        }
+        if j == bytes.len() {
+            return false;
+        }
+        if bytes[i + 1] == b'/' {
+            match depth.checked_sub(1) {
+                Some(a) => depth = a,
+                None => return false,
+            }
+        } else if bytes[j - 1] != b'/' {
+            depth += 1;
+        }
+        i = j + 1;
+        // the first element is closed, nothing is allowed after it
+        if depth == 0 {
+            return i == bytes.len();
+        }
+    }
+    false
+}
+
+/// Badge settings
+///
+/// ## Example
+///
+/// Polkadot badge with the letter `P`, in the top right corner.
+///
+/// ```
+/// use plot_icon::{badge::{Badge, Corner, Mark}, Style};
+///
+/// let badge = Badge {
+///     corner: Corner::TopRight,
+///     mark: Mark::Glyph('P'),
+///     ..Badge::for_network(0)
+/// };
+/// let style = Style{badge: Some(badge), ..Style::default()};
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Badge {
+    /// Image corner the badge is placed towards
+    pub corner: Corner,
+    /// Badge diameter, in parts of the image size
+    pub size: f32,
+    /// Disc color
+    pub color: Color,
+    /// What is drawn in the badge
+    pub mark: Mark,
+}
+
+impl Default for Badge {
+    /// Grey disc, 30% of the image size, in the bottom right corner
+    fn default() -> Self {
+        Self {
+            corner: Corner::default(),
+            size: 0.3,
+            color: DEFAULT_COLOR,
+            mark: Mark::default(),
+        }
+    }
+}
+
+impl Badge {
+    /// Default badge, with color of the network with given SS58 prefix
+    /// from [`NETWORK_COLORS`], if the network is known
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use plot_icon::{badge::Badge, Color};
+    ///
+    /// // polkadot
+    /// assert_eq!(Badge::for_network(0).color, Color{red: 230, green: 0, blue: 122, alpha: 255});
+    /// ```
+    pub fn for_network(ss58_prefix: u16) -> Self {
+        Self {
+            color: network_color(ss58_prefix).unwrap_or(DEFAULT_COLOR),
+            ..Self::default()
+        }
+    }
+
+    /// Check that the badge could be drawn in `png` and RGBA images; error
+    /// [`IdenticonError::UnsupportedLogo`] for `svg` logos, and for RGBA
+    /// logos without `pix` feature
+    ///
+    /// Checked by all `png` and RGBA generators accepting the style.
+    pub fn check_png(&self) -> Result<(), IdenticonError> {
+        match self.mark {
+            Mark::Svg(_) => Err(IdenticonError::UnsupportedLogo),
+            #[cfg(not(feature = "pix"))]
+            Mark::Rgba(_) => Err(IdenticonError::UnsupportedLogo),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Color of the network with given SS58 prefix, `None` if the network is
+/// not in [`NETWORK_COLORS`]
+pub fn network_color(ss58_prefix: u16) -> Option<Color> {
+    NETWORK_COLORS
+        .iter()
+        .find(|(prefix, _, _)| *prefix == ss58_prefix)
+        .map(|(_, _, color)| *color)
+}
+
+/// Badge center and radius, in coordinates of the identicon scene
+///
+/// Badge is centered on the outer circle of the identicon (first circle of
+/// the scene, or the circle inscribed into the view box if there is none),
+/// at 45 degrees towards the corner, and moved inwards if it does not fit
+/// into the view box.
+fn placement(badge: &Badge, scene: &Scene) -> (f32, f32, f32) {
+    let (min_x, min_y, width, height) = scene.view_box;
+    let (x_outer, y_outer, radius_outer) = scene
+        .elements
+        .iter()
+        .find_map(|element| match element {
+            Element::Circle {
+                x_center,
+                y_center,
+                radius,
+                ..
+            } => Some((*x_center, *y_center, *radius)),
+            _ => None,
+        })
+        .unwrap_or((
+            min_x + width / 2f32,
+            min_y + height / 2f32,
+            width.min(height) / 2f32,
+        ));
+    let radius = badge.size * width.min(height) / 2f32;
+    let offset = radius_outer * core::f32::consts::FRAC_1_SQRT_2;
+    let (x_center, y_center) = match badge.corner {
+        Corner::TopLeft => (x_outer - offset, y_outer - offset),
+        Corner::TopRight => (x_outer + offset, y_outer - offset),
+        Corner::BottomLeft => (x_outer - offset, y_outer + offset),
+        Corner::BottomRight => (x_outer + offset, y_outer + offset),
+    };
+    let fit = |center: f32, min: f32, length: f32| {
+        if 2f32 * radius >= length {
+            min + length / 2f32
+        } else {
+            center.clamp(min + radius, min + length - radius)
+        }
+    };
+    (
+        fit(x_center, min_x, width),
+        fit(y_center, min_y, height),
+        radius,
+    )
+}
+
+/// Glyph color: black or white, whichever is more visible on the background
+fn glyph_color(background: &Color) -> Color {
+    let luminance = 0.2126 * background.red as f32
+        + 0.7152 * background.green as f32
+        + 0.0722 * background.blue as f32;
+    let value = if luminance > 140f32 { 0 } else { 255 };
+    Color {
+        red: value,
+        green: value,
+        blue: value,
+        alpha: 255,
+    }
+}
+
+/// Glyph polygons, one rectangle for each horizontal run of font pixels,
+/// `None` if the glyph is not in the font
+fn glyph_polygons(
+    glyph: char,
+    x_center: f32,
+    y_center: f32,
+    height: f32,
+) -> Option<Vec<Vec<(f32, f32)>>> {
+    let index = match glyph.to_ascii_uppercase() {
+        a @ '0'..='9' => a as usize - '0' as usize,
+        a @ 'A'..='Z' => a as usize - 'A' as usize + 10,
+        _ => return None,
+    };
+    let cell = height / 7f32;
+    let left = x_center - 2.5 * cell;
+    let top = y_center - 3.5 * cell;
+    let mut polygons = Vec::new();
+    for (row, bits) in FONT[index].iter().enumerate() {
+        let mut column = 0;
+        while column < 5 {
+            if bits & (0x10 >> column) == 0 {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < 5 && bits & (0x10 >> column) != 0 {
+                column += 1;
+            }
+            let (x0, x1) = (left + start as f32 * cell, left + column as f32 * cell);
+            let (y0, y1) = (top + row as f32 * cell, top + (row + 1) as f32 * cell);
+            polygons.push(vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1)]);
+        }
+    }
+    Some(polygons)
+}
+
+/// Calculate badge [`Scene`] for the identicon scene: disc and glyph, with
+/// the same view box and no background
+///
+/// User logos are not part of the scene, disc is placed in the scene
+/// instead.
+pub fn calculate_scene(badge: &Badge, scene: &Scene) -> Scene {
+    let (x_center, y_center, radius) = placement(badge, scene);
+    let mut elements = vec![Element::Circle {
+        x_center,
+        y_center,
+        radius,
+        color: badge.color,
+    }];
+    if let Mark::Glyph(glyph) = badge.mark {
+        if let Some(polygons) =
+            glyph_polygons(glyph, x_center, y_center, 2f32 * radius * GLYPH_HEIGHT)
+        {
+            elements.push(Element::Path {
+                polygons,
+                color: glyph_color(&badge.color),
+            });
+        }
+    }
+    Scene {
+        view_box: scene.view_box,
+        background: None,
+        clip_to_circle: false,
+        elements,
+    }
+}
+
+/// Draw the badge over RGBA8 pixel data of the identicon with given scene,
+/// image is `size_in_pixels` wide, with given stride
+///
+/// Badge disc and glyph are anti-aliased, RGBA logo is scaled with
+/// `Triangle` filter. Logos that could not be drawn (see
+/// [`Badge::check_png`]) are replaced with the disc here, generators check
+/// the badge and return error for them instead.
+///
+/// # Panics
+///
+/// Panics if the buffer could not fit the image with given stride.
+pub fn draw_png_data(
+    badge: &Badge,
+    scene: &Scene,
+    size_in_pixels: u32,
+    data: &mut [u8],
+    stride: usize,
+) {
+    #[cfg(feature = "pix")]
+    if draw_png_logo(badge, scene, size_in_pixels, data, stride) {
+        return;
+    }
+    let size = size_in_pixels as usize;
+    let badge_data = calculate_scene(badge, scene).calculate_png_data(size_in_pixels);
+    for (j, row) in badge_data.chunks_exact(size * 4).enumerate() {
+        for (i, pixel) in row.chunks_exact(4).enumerate() {
+            if pixel[3] != 0 {
+                let start = j * stride + i * 4;
+                crate::raster::over(
+                    &mut data[start..start + 4],
+                    [pixel[0], pixel[1], pixel[2], pixel[3]],
+                );
+            }
+        }
+    }
+}
+
+/// Draw the RGBA logo of the badge, if there is a valid one, over RGBA8
+/// pixel data; `false` if nothing was drawn
+#[cfg(feature = "pix")]
+fn draw_png_logo(
+    badge: &Badge,
+    scene: &Scene,
+    size_in_pixels: u32,
+    data: &mut [u8],
+    stride: usize,
+) -> bool {
+    use image::{
+        imageops::{resize, FilterType},
+        RgbaImage,
+    };
+
+    let size = size_in_pixels as usize;
+    let logo = match &badge.mark {
+        Mark::Rgba(logo) => RgbaImage::from_raw(logo.width, logo.height, logo.data.to_vec())
+            .expect("logo data length is checked on creation"),
+        _ => return false,
+    };
+    let (x_center, y_center, radius) = placement(badge, scene);
+    let (min_x, min_y, width, _) = scene.view_box;
+    let scale = size_in_pixels as f32 / width;
+    let side = ((2f32 * radius * scale).round() as u32).max(1);
+    let left = ((x_center - radius - min_x) * scale).round() as i64;
+    let top = ((y_center - radius - min_y) * scale).round() as i64;
+    let logo = resize(&logo, side, side, FilterType::Triangle);
+    for (i, j, pixel) in logo.enumerate_pixels() {
+        let (x, y) = (left + i as i64, top + j as i64);
+        if x < 0 || y < 0 || x as usize >= size || y as usize >= size {
+            continue;
+        }
+        let start = y as usize * stride + x as usize * 4;
+        crate::raster::over(&mut data[start..start + 4], pixel.0);
+    }
+    true
+}
+
+/// Calculate `svg` elements of the badge for the identicon with given scene,
+/// to be placed over the identicon
+///
+/// `svg` logo is nested as `svg` element of the badge size. RGBA logo is
+/// embedded as `image` element with `png` data URI, with `pix` feature,
+/// and is replaced with the disc without it.
+#[cfg(feature = "vec")]
+pub fn calculate_svg_data(badge: &Badge, scene: &Scene) -> Vec<Box<dyn svg::Node>> {
+    let (x_center, y_center, radius) = placement(badge, scene);
+    match &badge.mark {
+        Mark::Svg(logo) => vec![Box::new(
+            element::SVG::new()
+                .set("x", x_center - radius)
+                .set("y", y_center - radius)
+                .set("width", 2f32 * radius)
+                .set("height", 2f32 * radius)
+                .add(Text::new(logo.as_str())),
+        )],
+        #[cfg(feature = "pix")]
+        Mark::Rgba(logo) => vec![Box::new(
+            element::Image::new()
+                .set("x", x_center - radius)
+                .set("y", y_center - radius)
+                .set("width", 2f32 * radius)
+                .set("height", 2f32 * radius)
+                .set("preserveAspectRatio", "none")
+                .set("href", png_data_uri(logo)),
+        )],
+        _ => calculate_scene(badge, scene).calculate_svg_data(),
+    }
+}
+
+/// RGBA logo as `png` data URI
+#[cfg(all(feature = "vec", feature = "pix"))]
+fn png_data_uri(logo: &RgbaLogo) -> String {
+    let mut png_data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_data, logo.width, logo.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&logo.data))
+        .expect("logo data length is checked on creation");
+    format!("data:image/png;base64,{}", base64(&png_data))
+}
+
+/// Standard base64 encoding, with padding
+#[cfg(all(feature = "vec", feature = "pix"))]
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, byte)| {
+            bits | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identicon_scene() -> Scene {
+        crate::circles::calculate_scene(10f32, [Color::foreground(); 19])
+    }
+
+    #[test]
+    fn placement_on_outer_circle() {
+        let badge = Badge {
+            size: 0.2,
+            ..Badge::default()
+        };
+        let (x, y, radius) = placement(&badge, &identicon_scene());
+        assert_eq!(radius, 2f32);
+        assert!((x - 10f32 * std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert_eq!(x, y);
+        // large badge is moved inwards to fit into the image
+        let badge = Badge {
+            corner: Corner::TopLeft,
+            size: 0.8,
+            ..Badge::default()
+        };
+        assert_eq!(placement(&badge, &identicon_scene()), (-2f32, -2f32, 8f32));
+    }
+
+    #[test]
+    fn known_networks() {
+        assert_eq!(Badge::for_network(2).color, network_color(2).unwrap());
+        assert_eq!(network_color(42), None);
+        assert_eq!(Badge::for_network(42).color, DEFAULT_COLOR);
+    }
+
+    #[test]
+    fn glyphs() {
+        // letter `L` is made of 7 rows, the last one is a single run
+        let polygons = glyph_polygons('l', 0f32, 0f32, 7f32).unwrap();
+        assert_eq!(polygons.len(), 7);
+        assert_eq!(
+            polygons[6],
+            vec![(-2.5, 2.5), (2.5, 2.5), (2.5, 3.5), (-2.5, 3.5)]
+        );
+        assert!(glyph_polygons('*', 0f32, 0f32, 7f32).is_none());
+        assert_eq!(glyph_color(&Color::foreground()).red, 0);
+        assert_eq!(glyph_color(&DEFAULT_COLOR).red, 255);
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn badge_over_pixels() {
+        let red = Color {
+            red: 255,
+            green: 0,
+            blue: 0,
+            alpha: 255,
+        };
+        let scene = identicon_scene();
+        // badge of 20x20 pixels is centered at pixel (90, 90) of 100x100 image
+        let center = (90 * 100 + 90) * 4;
+        for mark in [
+            Mark::Disc,
+            // svg logo is replaced with the disc
+            Mark::Svg(SvgLogo::new("<svg/>").unwrap()),
+        ] {
+            let badge = Badge {
+                size: 0.2,
+                color: red,
+                mark,
+                ..Badge::default()
+            };
+            let mut data = vec![0u8; 100 * 100 * 4];
+            draw_png_data(&badge, &scene, 100, &mut data, 400);
+            assert_eq!(data[center..center + 4], [255, 0, 0, 255]);
+            assert_eq!(data[0..4], [0, 0, 0, 0]);
+        }
+        let logo = Badge {
+            size: 0.2,
+            mark: Mark::Rgba(RgbaLogo::new(1, 1, vec![0, 0, 255, 255]).unwrap()),
+            ..Badge::default()
+        };
+        let mut data = vec![0u8; 100 * 100 * 4];
+        draw_png_data(&logo, &scene, 100, &mut data, 400);
+        assert_eq!(data[center..center + 4], [0, 0, 255, 255]);
+        // logo is square
+        assert_eq!(data[(81 * 100 + 81) * 4 + 3], 255);
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn badge_svg() {
+        let markup = "<svg viewBox=\"0 0 1 1\"/>";
+        let badge = Badge {
+            size: 0.2,
+            mark: Mark::Svg(SvgLogo::new(markup).unwrap()),
+            ..Badge::default()
+        };
+        let data = calculate_svg_data(&badge, &identicon_scene());
+        assert_eq!(data.len(), 1);
+        assert!(data[0].to_string().contains(markup));
+        let glyph = Badge {
+            mark: Mark::Glyph('K'),
+            ..badge
+        };
+        // disc and glyph path
+        assert_eq!(calculate_svg_data(&glyph, &identicon_scene()).len(), 2);
+    }
+
+    #[cfg(all(feature = "vec", feature = "pix"))]
+    #[test]
+    fn rgba_logo_svg() {
+        let badge = Badge {
+            size: 0.2,
+            mark: Mark::Rgba(RgbaLogo::new(1, 1, vec![0, 0, 255, 255]).unwrap()),
+            ..Badge::default()
+        };
+        let data = calculate_svg_data(&badge, &identicon_scene());
+        assert_eq!(data.len(), 1);
+        let image = data[0].to_string();
+        assert!(image.starts_with("<image"));
+        assert!(image.contains("href=\"data:image/png;base64,iVBORw0KGgo"));
+    }
+
+    #[cfg(all(feature = "vec", feature = "pix"))]
+    #[test]
+    fn base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn svg_logo_markup() {
+        for valid in [
+            "<svg/>",
+            "<svg viewBox=\"0 0 1 1\"></svg>",
+            " <svg><g title=\"a>b\"><path d=\"M0 0\"/></g><!-- <svg> --></svg>\n",
+        ] {
+            assert!(SvgLogo::new(valid).is_ok(), "{}", valid);
+        }
+        for invalid in [
+            "",
+            "<svgx/>",
+            "<?xml version=\"1.0\"?><svg/>",
+            "<svg></svg><script/>",
+            "<svg></svg>text",
+            "<svg><g></svg>",
+            "<svg></g></svg></svg>",
+            "<svg><!-- </svg>",
+        ] {
+            assert!(SvgLogo::new(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn logos_in_png() {
+        let svg = Badge {
+            mark: Mark::Svg(SvgLogo::new("<svg/>").unwrap()),
+            ..Badge::default()
+        };
+        assert!(matches!(
+            svg.check_png(),
+            Err(IdenticonError::UnsupportedLogo)
+        ));
+        let rgba = Badge {
+            mark: Mark::Rgba(RgbaLogo::new(1, 1, vec![0; 4]).unwrap()),
+            ..Badge::default()
+        };
+        assert_eq!(rgba.check_png().is_ok(), cfg!(feature = "pix"));
+        assert!(RgbaLogo::new(0, 0, Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,577 @@
+//! Beachball identicons, the `beachball` theme of polkadot.js
+//!
+//! The algorithm follows the published javascript
+//! [code](https://github.com/polkadot-js/ui/tree/master/packages/ui-shared/src/icons/beachball)
+//! of polkadot.js, that is based on [jazzicon](https://github.com/MetaMask/jazzicon):
+//! a set of colors is rotated by a random angle, the background and five
+//! overlapping shapes (squares, or circles in alternative variant) get the
+//! colors from the set, shapes are moved and rotated randomly, and all is
+//! clipped by a circle.
+//!
+//! Random numbers are taken from the seed bytes. Polkadot.js uses the bytes
+//! of SS58 address string as seed.
+#[cfg(feature = "vec")]
+use svg::node::element;
+
+use crate::colors::Color;
+use crate::scene::{Element, Scene};
+
+/// Number of shapes over the background
+const SHAPE_COUNT: usize = 5;
+
+/// Range of color set hue rotation, in degrees
+const WOBBLE: f64 = 30.0;
+
+/// Colors from jazzicon, in RGB format
+const COLORS: [[u8; 3]; 10] = [
+    [0x01, 0x88, 0x8c], // teal
+    [0xfc, 0x75, 0x00], // bright orange
+    [0x03, 0x4f, 0x5d], // dark teal
+    [0xf7, 0x3f, 0x01], // orangered
+    [0xfc, 0x19, 0x60], // magenta
+    [0xc7, 0x14, 0x4c], // raspberry
+    [0xf3, 0xc1, 0x00], // goldenrod
+    [0x15, 0x98, 0xf2], // lightning blue
+    [0x24, 0x65, 0xe1], // sail blue
+    [0xf1, 0x9e, 0x02], // gold
+];
+
+/// Shapes drawn over the background
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShapeKind {
+    /// Rotated squares, default polkadot.js look
+    #[default]
+    Square,
+    /// Circles, alternative polkadot.js look
+    Circle,
+}
+
+/// Color in HSL format, as javascript `color` package keeps it
+///
+/// Hue is in degrees, saturation and lightness are in percents.
+/// All components are rounded to one decimal place.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HslColor {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+impl HslColor {
+    /// Color from RGB components, javascript `color-convert` `rgb.hsl`
+    fn from_rgb(rgb: [u8; 3]) -> Self {
+        let [r, g, b] = rgb.map(|a| a as f64 / 255f64);
+        let min = r.min(g).min(b);
+        let max = r.max(g).max(b);
+        let delta = max - min;
+        let hue = if max == min {
+            0f64
+        } else if r == max {
+            (g - b) / delta
+        } else if g == max {
+            2f64 + (b - r) / delta
+        } else {
+            4f64 + (r - g) / delta
+        };
+        let mut hue = (hue * 60f64).min(360f64);
+        if hue < 0f64 {
+            hue += 360f64;
+        }
+        let lightness = (min + max) / 2f64;
+        let saturation = if max == min {
+            0f64
+        } else if lightness <= 0.5 {
+            delta / (max + min)
+        } else {
+            delta / (2f64 - max - min)
+        };
+        Self {
+            hue,
+            saturation: saturation * 100f64,
+            lightness: lightness * 100f64,
+        }
+    }
+
+    /// Rotate hue by given angle, javascript `color` `rotate`
+    fn rotate(&self, degrees: f64) -> Self {
+        let hue = (self.hue + degrees) % 360f64;
+        Self {
+            hue: if hue < 0f64 { 360f64 + hue } else { hue },
+            ..*self
+        }
+    }
+
+    /// Round components to one decimal place, javascript `color` `round`
+    fn round(&self) -> Self {
+        Self {
+            hue: round_to_place(self.hue, 1),
+            saturation: round_to_place(self.saturation, 1),
+            lightness: round_to_place(self.lightness, 1),
+        }
+    }
+
+    /// CSS color string, same as javascript `color` `string` makes it
+    pub fn to_css(&self) -> String {
+        format!(
+            "hsl({}, {}%, {}%)",
+            self.hue, self.saturation, self.lightness
+        )
+    }
+
+    /// Convert into [`Color`], the way CSS color is rendered
+    pub fn to_color(&self) -> Color {
+        let s = self.saturation / 100f64;
+        let l = self.lightness / 100f64;
+        let component = |n: f64| {
+            let k = (n + self.hue / 30f64) % 12f64;
+            let a = s * l.min(1f64 - l);
+            ((l - a * (k - 3f64).min(9f64 - k).clamp(-1f64, 1f64)) * 255f64).round() as u8
+        };
+        Color {
+            red: component(0f64),
+            green: component(8f64),
+            blue: component(4f64),
+            alpha: 255,
+        }
+    }
+}
+
+/// Number formatted with fixed number of decimal places,
+/// javascript `Number.prototype.toFixed`
+///
+/// Exact ties are rounded away from zero, unlike in Rust formatting.
+pub(crate) fn to_fixed(value: f64, places: usize) -> String {
+    let exact = format!("{:.*}", places + 80, value.abs());
+    let cut = exact.find('.').expect("formatted with decimal places") + places + 1;
+    let is_tie = exact[cut..].starts_with('5') && exact[cut + 1..].bytes().all(|a| a == b'0');
+    let magnitude = if is_tie {
+        value.abs() + 0.5 * 10f64.powi(-(places as i32))
+    } else {
+        value.abs()
+    };
+    let out = format!("{:.*}", places, magnitude);
+    if value < 0f64 && out.bytes().any(|a| a != b'0' && a != b'.') {
+        format!("-{}", out)
+    } else {
+        out
+    }
+}
+
+/// Number rounded to given decimal places, javascript `Number(value.toFixed(places))`
+fn round_to_place(value: f64, places: usize) -> f64 {
+    to_fixed(value, places)
+        .parse()
+        .expect("formatted number is parsed")
+}
+
+/// Random numbers from the seed bytes, javascript `newSeeder`
+struct Seeder<'a> {
+    seed: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Seeder<'a> {
+    fn new(seed: &'a [u8]) -> Self {
+        // first byte to read is selected by the byte in the middle of the seed
+        let index = seed[seed.len() / 2] as usize % seed.len();
+        Self { seed, index }
+    }
+
+    /// Next random number in range `[0, 1)`, from two seed bytes
+    fn next(&mut self) -> f64 {
+        let mut result = 0u32;
+        for _ in 0..2 {
+            result = result * 256 + self.seed[self.index] as u32;
+            self.index = (self.index + 1) % self.seed.len();
+        }
+        result as f64 / 65536f64
+    }
+}
+
+/// Figure of the beachball shape
+#[derive(Clone, Debug, PartialEq)]
+pub enum Figure {
+    /// Square of the icon size, rotated around the icon center by `rotation`
+    /// degrees clockwise, and then moved by `translate`
+    ///
+    /// Values are rounded as in javascript `svg` transform attribute.
+    Square {
+        translate: (f64, f64),
+        rotation: f64,
+    },
+    /// Circle
+    Circle { cx: f64, cy: f64, radius: f64 },
+}
+
+/// Beachball shape: figure and its color
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape {
+    pub color: HslColor,
+    pub figure: Figure,
+}
+
+/// Beachball identicon for given icon size
+#[derive(Clone, Debug, PartialEq)]
+pub struct Beachball {
+    pub size_in_pixels: u32,
+    pub background: HslColor,
+    /// Shapes in rendering order
+    pub shapes: Vec<Shape>,
+}
+
+/// Calculate beachball identicon from the seed bytes, for given icon size
+///
+/// Empty seed is replaced with 32 zero bytes, same as javascript default seed.
+pub fn get_beachball(seed: &[u8], size_in_pixels: u32, kind: ShapeKind) -> Beachball {
+    let seed = if seed.is_empty() { &[0u8; 32] } else { seed };
+    let mut seeder = Seeder::new(seed);
+    let size = size_in_pixels as f64;
+
+    let amount = seeder.next() * WOBBLE - WOBBLE / 2f64;
+    let mut colors: Vec<HslColor> = COLORS
+        .iter()
+        .map(|a| HslColor::from_rgb(*a).rotate(amount))
+        .collect();
+    let mut next_color = |seeder: &mut Seeder| {
+        let index = (colors.len() as f64 * seeder.next()).floor() as usize;
+        colors.remove(index).round()
+    };
+
+    let background = next_color(&mut seeder);
+    let mut shapes: Vec<Shape> = Vec::with_capacity(SHAPE_COUNT);
+    for count in 0..SHAPE_COUNT {
+        let color = next_color(&mut seeder);
+        let figure = match kind {
+            ShapeKind::Square => {
+                let first_rotation = seeder.next();
+                let angle = std::f64::consts::PI * 2f64 * first_rotation;
+                let scale = count as f64 / SHAPE_COUNT as f64;
+                let velocity = (size / SHAPE_COUNT as f64) * seeder.next() + scale * size;
+                let rotation = first_rotation * 360f64 + seeder.next() * 180f64;
+                Figure::Square {
+                    translate: (
+                        round_to_place(libm::cos(angle) * velocity, 3),
+                        round_to_place(libm::sin(angle) * velocity, 3),
+                    ),
+                    rotation: round_to_place(rotation, 1),
+                }
+            }
+            ShapeKind::Circle => {
+                let center = size / 2f64;
+                // javascript code uses angle in degrees as angle in radians
+                let angle = seeder.next() * 360f64;
+                let radius = ((SHAPE_COUNT - count) as f64 / SHAPE_COUNT as f64) * (size / 2f64)
+                    + (size / 8f64) * seeder.next();
+                let offset =
+                    (size / 4f64) * (seeder.next() + (count + 1) as f64 / SHAPE_COUNT as f64);
+                // `libm` trigonometry follows fdlibm, as javascript engines
+                // mostly do; results could still differ in the last bit
+                Figure::Circle {
+                    cx: offset * libm::sin(angle) + center,
+                    cy: offset * libm::cos(angle) + center,
+                    radius,
+                }
+            }
+        };
+        shapes.push(Shape { color, figure });
+    }
+    Beachball {
+        size_in_pixels,
+        background,
+        shapes,
+    }
+}
+
+/// `svg` transform attribute for the square, as javascript makes it
+pub fn square_transform(size_in_pixels: u32, translate: (f64, f64), rotation: f64) -> String {
+    let center = size_in_pixels as f64 / 2f64;
+    format!(
+        "translate({} {}) rotate({} {} {})",
+        to_fixed(translate.0, 3),
+        to_fixed(translate.1, 3),
+        to_fixed(rotation, 1),
+        center,
+        center
+    )
+}
+
+/// Calculate `svg` elements: background circle and the group of shapes
+/// clipped by the background circle
+///
+/// `clip_id` is the identifier of the clip path, it should be unique within
+/// the document.
+#[cfg(feature = "vec")]
+pub fn calculate_svg_data(beachball: &Beachball, clip_id: &str) -> Vec<Box<dyn svg::Node>> {
+    let size = beachball.size_in_pixels;
+    let center = size as f64 / 2f64;
+    let circle = || {
+        element::Circle::new()
+            .set("cx", center)
+            .set("cy", center)
+            .set("r", center)
+    };
+    let clip_path = element::ClipPath::new().set("id", clip_id).add(circle());
+    let mut group = element::Group::new().set("clip-path", format!("url(#{})", clip_id));
+    for shape in beachball.shapes.iter() {
+        group = match shape.figure {
+            Figure::Square {
+                translate,
+                rotation,
+            } => group.add(
+                element::Rectangle::new()
+                    .set("x", 0)
+                    .set("y", 0)
+                    .set("width", size)
+                    .set("height", size)
+                    .set("transform", square_transform(size, translate, rotation))
+                    .set("fill", shape.color.to_css()),
+            ),
+            Figure::Circle { cx, cy, radius } => group.add(
+                element::Circle::new()
+                    .set("cx", cx)
+                    .set("cy", cy)
+                    .set("r", radius)
+                    .set("fill", shape.color.to_css()),
+            ),
+        };
+    }
+    vec![
+        Box::new(element::Definitions::new().add(clip_path)),
+        Box::new(circle().set("fill", "white")),
+        Box::new(circle().set("fill", beachball.background.to_css())),
+        Box::new(group),
+    ]
+}
+
+/// Polygon of the transformed square, in pixel coordinates
+fn square_outline(size: f64, translate: (f64, f64), rotation: f64) -> Vec<(f64, f64)> {
+    let center = size / 2f64;
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    [(0f64, 0f64), (size, 0f64), (size, size), (0f64, size)]
+        .iter()
+        .map(|(x, y)| {
+            (
+                center + (x - center) * cos - (y - center) * sin + translate.0,
+                center + (x - center) * sin + (y - center) * cos + translate.1,
+            )
+        })
+        .collect()
+}
+
+/// Calculate [`Scene`]: white and background circles under the shapes,
+/// all clipped by the icon circle, in pixel coordinates
+pub fn calculate_scene(beachball: &Beachball) -> Scene {
+    let size = beachball.size_in_pixels as f32;
+    let circle = |color: Color| Element::Circle {
+        x_center: size / 2f32,
+        y_center: size / 2f32,
+        radius: size / 2f32,
+        color,
+    };
+    let white = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+        alpha: 255,
+    };
+    let mut elements = vec![circle(white), circle(beachball.background.to_color())];
+    for shape in beachball.shapes.iter() {
+        let color = shape.color.to_color();
+        elements.push(match shape.figure {
+            Figure::Square {
+                translate,
+                rotation,
+            } => Element::Path {
+                polygons: vec![square_outline(size as f64, translate, rotation)
+                    .into_iter()
+                    .map(|(x, y)| (x as f32, y as f32))
+                    .collect()],
+                color,
+            },
+            Figure::Circle { cx, cy, radius } => Element::Circle {
+                x_center: cx as f32,
+                y_center: cy as f32,
+                radius: radius as f32,
+                color,
+            },
+        });
+    }
+    Scene {
+        view_box: (0f32, 0f32, size, size),
+        background: None,
+        clip_to_circle: true,
+        elements,
+    }
+}
+
+/// Calculate `png` image pixel data (only pixel colors)
+///
+/// Shapes are anti-aliased and clipped by the icon circle,
+/// background outside of the circle is transparent.
+#[cfg(feature = "pix")]
+pub fn calculate_png_data(beachball: &Beachball) -> Vec<u8> {
+    let size = beachball.size_in_pixels;
+    let center = size as f64 / 2f64;
+    let mut data = vec![0u8; size as usize * size as usize * 4];
+    let clip = crate::raster::coverage(
+        size,
+        &[crate::raster::ellipse(center, center, center, center)],
+    );
+    let white = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+        alpha: 255,
+    };
+    // white circle under the background circle, as in javascript containers
+    for color in [white, beachball.background.to_color()] {
+        crate::raster::composite(&mut data, &clip, color);
+    }
+    for shape in beachball.shapes.iter() {
+        let outline = match shape.figure {
+            Figure::Square {
+                translate,
+                rotation,
+            } => square_outline(size as f64, translate, rotation),
+            Figure::Circle { cx, cy, radius } => crate::raster::ellipse(cx, cy, radius, radius),
+        };
+        let coverage = crate::raster::intersect(&crate::raster::coverage(size, &[outline]), &clip);
+        crate::raster::composite(&mut data, &coverage, shape.color.to_color());
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE_ADDRESS: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE: &[u8] = &[
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    #[test]
+    fn javascript_number_formatting() {
+        assert_eq!(to_fixed(0.25, 1), "0.3");
+        assert_eq!(to_fixed(2.5, 0), "3");
+        assert_eq!(to_fixed(-1.0005, 3), "-1.000");
+        assert_eq!(to_fixed(-0.0001, 3), "0.000");
+        assert_eq!(to_fixed(12.3, 3), "12.300");
+        assert_eq!(round_to_place(100.0, 1).to_string(), "100");
+    }
+
+    /// Fixture made with javascript polkadot.js beachball code
+    #[test]
+    fn squares_fixture() {
+        let beachball = get_beachball(ALICE_ADDRESS.as_bytes(), 64, ShapeKind::Square);
+        assert_eq!(beachball.background.to_css(), "hsl(185.7, 93.7%, 18.8%)");
+        let expected = [
+            (
+                "hsl(337.6, 81.7%, 42.9%)",
+                "translate(-2.019 1.813) rotate(224.0 32 32)",
+            ),
+            (
+                "hsl(337.6, 97.4%, 54.3%)",
+                "translate(-6.731 14.754) rotate(173.0 32 32)",
+            ),
+            (
+                "hsl(24.3, 100%, 49.4%)",
+                "translate(-28.491 8.437) rotate(242.5 32 32)",
+            ),
+            (
+                "hsl(44.1, 100%, 47.6%)",
+                "translate(-23.793 35.118) rotate(179.3 32 32)",
+            ),
+            (
+                "hsl(11.5, 99.2%, 48.6%)",
+                "translate(-15.822 54.776) rotate(168.8 32 32)",
+            ),
+        ];
+        for (shape, (fill, transform)) in beachball.shapes.iter().zip(expected) {
+            assert_eq!(shape.color.to_css(), fill);
+            match shape.figure {
+                Figure::Square {
+                    translate,
+                    rotation,
+                } => assert_eq!(square_transform(64, translate, rotation), transform),
+                _ => panic!("expected square"),
+            }
+        }
+    }
+
+    /// Fixture made with javascript polkadot.js beachball code
+    #[test]
+    fn circles_fixture() {
+        let beachball = get_beachball(ALICE, 30, ShapeKind::Circle);
+        assert_eq!(beachball.background.hue, 184.0);
+        let expected = [
+            (
+                (221.7, 75.9, 51.2),
+                (13.380215177554721, 14.766502519706865, 15.391674041748047),
+            ),
+            (
+                (343.5, 81.7, 42.9),
+                (20.150300381253913, 19.599558983701712, 13.953277587890625),
+            ),
+            (
+                (206.8, 89.5, 51.6),
+                (12.55190442283501, 5.979849595303682, 10.94681167602539),
+            ),
+            (
+                (343.6, 97.4, 54.3),
+                (15.233590706048433, 8.35991953402592, 8.16470718383789),
+            ),
+            (
+                (41.5, 98.4, 47.6),
+                (7.441600717685693, 13.91043108750363, 3.391674041748047),
+            ),
+        ];
+        for (shape, (hsl, circle)) in beachball.shapes.iter().zip(expected) {
+            assert_eq!(
+                (
+                    shape.color.hue,
+                    shape.color.saturation,
+                    shape.color.lightness
+                ),
+                hsl
+            );
+            assert_eq!(
+                shape.figure,
+                Figure::Circle {
+                    cx: circle.0,
+                    cy: circle.1,
+                    radius: circle.2
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn css_color_conversion() {
+        let color = HslColor {
+            hue: 185.7,
+            saturation: 93.7,
+            lightness: 18.8,
+        };
+        assert_eq!(
+            color.to_color(),
+            Color {
+                red: 3,
+                green: 84,
+                blue: 93,
+                alpha: 255
+            }
+        );
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn png_is_clipped_by_circle() {
+        let beachball = get_beachball(ALICE_ADDRESS.as_bytes(), 64, ShapeKind::Square);
+        let data = calculate_png_data(&beachball);
+        // corner is outside of the circle, center is opaque
+        assert_eq!(data[3], 0);
+        assert_eq!(data[(32 * 64 + 32) * 4 + 3], 255);
+    }
+}
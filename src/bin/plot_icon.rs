@@ -0,0 +1,361 @@
+//! Command-line identicon generator
+//!
+//! Generates identicons in `png` or `svg` format for hexadecimal
+//! public keys and SS58 addresses, given as arguments, read from files,
+//! or read from standard input in batch mode. Run with `--help` for usage.
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use image::imageops::FilterType;
+use plot_icon::{
+    generate_png_scaled_custom, generate_png_with_theme, generate_svg_with_theme, input,
+    theme::{Beachball, IconTheme, Theme},
+};
+
+const USAGE: &str = "Usage: plot-icon [OPTIONS] [KEY]...
+
+Generates identicons for hexadecimal public keys (with or without 0x)
+and SS58 addresses.
+
+Arguments:
+  [KEY]...               public keys or SS58 addresses
+
+Options:
+  -i, --input <FILE>     read keys from file, one key per line
+  -b, --batch            read keys from standard input, one key per line
+  -f, --format <FORMAT>  output format, png or svg [default: png]
+  -t, --theme <THEME>    icon theme: polkadot, substrate, beachball,
+                         ethereum or empty [default: polkadot]
+  -s, --size <SIZE>      png image size in pixels [default: 30]
+  -k, --scale <SCALE>    png scaling factor [default: 5]
+      --filter <FILTER>  png resize filter: nearest, triangle, catmullrom,
+                         gaussian or lanczos3 [default: lanczos3]
+  -o, --out <PATH>       output file for single key, output directory for
+                         several keys; single icon goes to standard output
+                         if not set
+  -h, --help             print this help
+
+Empty lines and lines starting with # in key files and standard input
+are skipped.";
+
+/// Output image format
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Png,
+    Svg,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match &self {
+            Format::Png => "png",
+            Format::Svg => "svg",
+        }
+    }
+}
+
+/// Parsed command-line options
+#[derive(Debug, PartialEq)]
+struct Options {
+    keys: Vec<String>,
+    input_files: Vec<PathBuf>,
+    batch: bool,
+    format: Format,
+    theme: IconTheme,
+    size_in_pixels: u32,
+    scaling_factor: u32,
+    filter_type: FilterType,
+    out: Option<PathBuf>,
+    help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            input_files: Vec::new(),
+            batch: false,
+            format: Format::Png,
+            theme: IconTheme::Polkadot,
+            size_in_pixels: 30,
+            scaling_factor: 5,
+            filter_type: FilterType::Lanczos3,
+            out: None,
+            help: false,
+        }
+    }
+}
+
+/// Parse command-line arguments, not including the program name
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        // both `--size 30` and `--size=30` are accepted
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or(format!("Missing value for {}.", flag))
+        };
+        match flag.as_str() {
+            "-h" | "--help" => options.help = true,
+            "-b" | "--batch" => options.batch = true,
+            "-i" | "--input" => options.input_files.push(PathBuf::from(value()?)),
+            "-o" | "--out" => options.out = Some(PathBuf::from(value()?)),
+            "-f" | "--format" => options.format = parse_format(&value()?)?,
+            "-t" | "--theme" => options.theme = value()?.parse().map_err(|e| format!("{}", e))?,
+            "-s" | "--size" => options.size_in_pixels = parse_number(&value()?, "size")?,
+            "-k" | "--scale" => options.scaling_factor = parse_number(&value()?, "scale")?,
+            "--filter" => options.filter_type = parse_filter(&value()?)?,
+            a if a.starts_with('-') && a.len() > 1 => return Err(format!("Unknown option {}.", a)),
+            _ => options.keys.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value.to_lowercase().as_str() {
+        "png" => Ok(Format::Png),
+        "svg" => Ok(Format::Svg),
+        _ => Err(format!("Unknown format {}, expected png or svg.", value)),
+    }
+}
+
+fn parse_number(value: &str, name: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {} {}, expected positive integer.", name, value))
+}
+
+fn parse_filter(value: &str) -> Result<FilterType, String> {
+    match value.to_lowercase().as_str() {
+        "nearest" => Ok(FilterType::Nearest),
+        "triangle" => Ok(FilterType::Triangle),
+        "catmullrom" => Ok(FilterType::CatmullRom),
+        "gaussian" => Ok(FilterType::Gaussian),
+        "lanczos3" => Ok(FilterType::Lanczos3),
+        _ => Err(format!("Unknown filter {}.", value)),
+    }
+}
+
+/// Collect keys from lines, skipping empty lines and comments
+fn keys_from_lines<I: IntoIterator<Item = String>>(lines: I) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/// Generate identicon file content for a single key
+///
+/// Polkadot `png` identicons are scaled down from larger image,
+/// other themes are rendered in requested size directly. Beachball for SS58
+/// address is seeded with the address network prefix, as in polkadot.js.
+fn render(key: &str, options: &Options) -> Result<Vec<u8>, String> {
+    let decoded = input::decode(key).map_err(|e| format!("{}: {}", key, e))?;
+    let theme: Box<dyn Theme> = match (options.theme, decoded.prefix) {
+        (IconTheme::Beachball, Some(ss58_prefix)) => {
+            Box::new(Beachball::new(ss58_prefix).map_err(|e| format!("{}: {}", key, e))?)
+        }
+        (theme, _) => theme.theme(),
+    };
+    let size_in_pixels = || {
+        u16::try_from(options.size_in_pixels).map_err(|_| {
+            format!(
+                "Size {} is too large for {} theme.",
+                options.size_in_pixels, options.theme
+            )
+        })
+    };
+    match (options.format, options.theme) {
+        (Format::Png, IconTheme::Polkadot) => generate_png_scaled_custom(
+            &decoded.account,
+            options.size_in_pixels,
+            options.scaling_factor,
+            options.filter_type,
+        )
+        .map_err(|e| format!("{}: {}", key, e)),
+        (Format::Png, _) => {
+            generate_png_with_theme(theme.as_ref(), &decoded.account, size_in_pixels()?)
+                .map_err(|e| format!("{}: {}", key, e))
+        }
+        (Format::Svg, _) => {
+            Ok(
+                generate_svg_with_theme(theme.as_ref(), &decoded.account, size_in_pixels()?)
+                    .to_string()
+                    .into_bytes(),
+            )
+        }
+    }
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let mut keys = options.keys.clone();
+    for file in options.input_files.iter() {
+        let content = fs::read_to_string(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+        keys.extend(keys_from_lines(content.lines().map(String::from)));
+    }
+    if options.batch {
+        let lines = io::stdin()
+            .lock()
+            .lines()
+            .collect::<Result<Vec<String>, io::Error>>()
+            .map_err(|e| format!("Error reading standard input: {}", e))?;
+        keys.extend(keys_from_lines(lines));
+    }
+
+    match (keys.len(), &options.out) {
+        (0, _) => Err(String::from("No keys provided.")),
+        (1, None) => io::stdout()
+            .write_all(&render(&keys[0], &options)?)
+            .map_err(|e| format!("Error writing standard output: {}", e)),
+        (1, Some(path)) if !path.is_dir() => write_file(path, &render(&keys[0], &options)?),
+        (_, None) => Err(String::from(
+            "Output directory (--out) is required for several keys.",
+        )),
+        (_, Some(dir)) => {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+            let mut failed = 0;
+            for key in keys.iter() {
+                let path = dir.join(format!("{}.{}", key, options.format.extension()));
+                if let Err(e) =
+                    render(key, &options).and_then(|content| write_file(&path, &content))
+                {
+                    eprintln!("{}", e);
+                    failed += 1;
+                }
+            }
+            match failed {
+                0 => Ok(()),
+                a => Err(format!(
+                    "Failed to generate {} of {} identicons.",
+                    a,
+                    keys.len()
+                )),
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    match run(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_all_options() {
+        let options = parse_args(args(
+            "-f svg -t beachball --size=64 -k 8 --filter CatmullRom -o icons -i keys.txt --batch 0xd435",
+        ))
+        .unwrap();
+        assert_eq!(
+            options,
+            Options {
+                keys: vec![String::from("0xd435")],
+                input_files: vec![PathBuf::from("keys.txt")],
+                batch: true,
+                format: Format::Svg,
+                theme: IconTheme::Beachball,
+                size_in_pixels: 64,
+                scaling_factor: 8,
+                filter_type: FilterType::CatmullRom,
+                out: Some(PathBuf::from("icons")),
+                help: false,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse_args(args("--format jpg")).is_err());
+        assert!(parse_args(args("--size")).is_err());
+        assert!(parse_args(args("--size -3")).is_err());
+        assert!(parse_args(args("--unknown")).is_err());
+        assert!(parse_args(args("--theme jazzicon")).is_err());
+    }
+
+    #[test]
+    fn beachball_with_address_prefix() {
+        let alice =
+            input::decode_hex("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
+                .unwrap();
+        let polkadot_address = input::encode_ss58(&alice, 0).unwrap();
+        assert_eq!(
+            polkadot_address,
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        let options = Options {
+            format: Format::Svg,
+            theme: IconTheme::Beachball,
+            size_in_pixels: 64,
+            ..Options::default()
+        };
+        let seeded = plot_icon::generate_svg_beachball(polkadot_address.as_bytes(), 64)
+            .to_string()
+            .into_bytes();
+        assert_eq!(render(&polkadot_address, &options).unwrap(), seeded);
+        // hexadecimal key has no prefix, default one is used
+        let hex_key = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+        assert_ne!(render(hex_key, &options).unwrap(), seeded);
+        assert_eq!(
+            render(hex_key, &options).unwrap(),
+            render("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", &options).unwrap()
+        );
+    }
+
+    #[test]
+    fn lines_with_comments() {
+        let lines = [
+            "# header",
+            "",
+            "  5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY  ",
+        ];
+        assert_eq!(
+            keys_from_lines(lines.iter().map(|a| a.to_string())),
+            vec![String::from(
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+            )]
+        );
+    }
+}
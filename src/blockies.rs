@@ -0,0 +1,323 @@
+//! Ethereum "blockies" identicons, for 20-byte accounts
+//!
+//! Polkadot.js displays Ethereum-style 20-byte accounts (e.g. Moonbeam)
+//! with blockies theme. The algorithm follows the published javascript
+//! [code](https://github.com/MyCryptoHQ/ethereum-blockies-base64) used by
+//! polkadot.js: xorshift random generator is seeded with lowercase
+//! hexadecimal address (with `0x`), three colors are generated in HSL
+//! format, and 8x8 grid of cells is filled, with left half of each row
+//! mirrored into the right half.
+#[cfg(feature = "vec")]
+use svg::node::element;
+
+use crate::colors::Color;
+use crate::scene::{Element, Scene};
+
+/// Number of cells in blockies grid row or column
+pub const GRID_SIZE: usize = 8;
+
+/// Blockies identicon: three colors and the grid of cells
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Blockies {
+    /// Color of the cells marked `1`
+    pub color: Color,
+    /// Color of the cells marked `0`
+    pub background: Color,
+    /// Color of the cells marked `2`
+    pub spot_color: Color,
+    /// Grid of cells, row by row
+    pub grid: [[u8; GRID_SIZE]; GRID_SIZE],
+}
+
+impl Blockies {
+    /// Color of the cell in given row and column
+    pub fn cell_color(&self, row: usize, column: usize) -> Color {
+        match self.grid[row][column] {
+            0 => self.background,
+            1 => self.color,
+            _ => self.spot_color,
+        }
+    }
+}
+
+/// Xorshift random generator, with javascript number semantics
+struct Random {
+    seed: [i64; 4],
+}
+
+impl Random {
+    /// Seed the generator with the string, as javascript `seedrand` does
+    ///
+    /// Javascript numbers are doubles, and only shift operations convert
+    /// them into 32-bit integers, therefore the seed values are kept in `i64`.
+    fn new(seed: &str) -> Self {
+        let mut random = Self { seed: [0; 4] };
+        for (i, c) in seed.encode_utf16().enumerate() {
+            let value = random.seed[i % 4];
+            random.seed[i % 4] = ((value as i32).wrapping_shl(5) as i64) - value + c as i64;
+        }
+        random
+    }
+
+    /// Next random number in range `[0, 1)`
+    fn next(&mut self) -> f64 {
+        let first = self.seed[0] as i32;
+        let t = first ^ first.wrapping_shl(11);
+        self.seed[0] = self.seed[1];
+        self.seed[1] = self.seed[2];
+        self.seed[2] = self.seed[3];
+        let last = self.seed[3] as i32;
+        let new = last ^ (last >> 19) ^ t ^ (t >> 8);
+        self.seed[3] = new as i64;
+        (new as u32) as f64 / (1u32 << 31) as f64
+    }
+
+    /// Random color, as javascript `createColor` and `hsl2rgb` produce it
+    fn color(&mut self) -> Color {
+        let h = (self.next() * 360f64).floor();
+        let s = self.next() * 60f64 + 40f64;
+        let l = (self.next() + self.next() + self.next() + self.next()) * 25f64;
+        hsl_to_color(h / 360f64, s / 100f64, l / 100f64)
+    }
+}
+
+/// Convert HSL color with all components in range `[0, 1]` into [`Color`]
+fn hsl_to_color(h: f64, s: f64, l: f64) -> Color {
+    let (red, green, blue) = if s == 0f64 {
+        (l, l, l)
+    } else {
+        let q = if l < 0.5 {
+            l * (1f64 + s)
+        } else {
+            l + s - l * s
+        };
+        let p = 2f64 * l - q;
+        (
+            hue_to_component(p, q, h + 1f64 / 3f64),
+            hue_to_component(p, q, h),
+            hue_to_component(p, q, h - 1f64 / 3f64),
+        )
+    };
+    Color {
+        red: (red * 255f64).round() as u8,
+        green: (green * 255f64).round() as u8,
+        blue: (blue * 255f64).round() as u8,
+        alpha: 255,
+    }
+}
+
+fn hue_to_component(p: f64, q: f64, t: f64) -> f64 {
+    let t = if t < 0f64 {
+        t + 1f64
+    } else if t > 1f64 {
+        t - 1f64
+    } else {
+        t
+    };
+    if t < 1f64 / 6f64 {
+        p + (q - p) * 6f64 * t
+    } else if t < 1f64 / 2f64 {
+        q
+    } else if t < 2f64 / 3f64 {
+        p + (q - p) * (2f64 / 3f64 - t) * 6f64
+    } else {
+        p
+    }
+}
+
+/// Seed string for 20-byte account: lowercase hexadecimal with `0x`
+pub fn seed_from_account(account: &[u8]) -> String {
+    let mut seed = String::from("0x");
+    for byte in account.iter() {
+        seed.push_str(&format!("{:02x}", byte));
+    }
+    seed
+}
+
+/// Calculate blockies identicon from account bytes
+///
+/// Typical input is 20-byte Ethereum-style account.
+pub fn get_blockies(account: &[u8]) -> Blockies {
+    get_blockies_from_seed(&seed_from_account(account))
+}
+
+/// Calculate blockies identicon from seed string
+///
+/// Seed is converted into lowercase, so that checksummed Ethereum
+/// addresses produce the same identicon as plain lowercase ones.
+pub fn get_blockies_from_seed(seed: &str) -> Blockies {
+    let mut random = Random::new(&seed.to_lowercase());
+    let color = random.color();
+    let background = random.color();
+    let spot_color = random.color();
+    let mut grid = [[0u8; GRID_SIZE]; GRID_SIZE];
+    for row in grid.iter_mut() {
+        for x in 0..GRID_SIZE / 2 {
+            let value = (random.next() * 2.3f64).floor() as u8;
+            row[x] = value;
+            row[GRID_SIZE - 1 - x] = value;
+        }
+    }
+    Blockies {
+        color,
+        background,
+        spot_color,
+        grid,
+    }
+}
+
+/// Calculate [`Scene`], grid cell is a unit square
+///
+/// Background square covers the whole grid, cells of each other color
+/// make a single path over it.
+pub fn calculate_scene(blockies: &Blockies) -> Scene {
+    let grid_size = GRID_SIZE as f32;
+    let square = |x: f32, y: f32, side: f32| {
+        vec![(x, y), (x + side, y), (x + side, y + side), (x, y + side)]
+    };
+    let mut elements = vec![Element::Path {
+        polygons: vec![square(0f32, 0f32, grid_size)],
+        color: blockies.background,
+    }];
+    for (value, color) in [(1, blockies.color), (2, blockies.spot_color)] {
+        let mut polygons = Vec::new();
+        for (row, cells) in blockies.grid.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                if *cell == value {
+                    polygons.push(square(column as f32, row as f32, 1f32));
+                }
+            }
+        }
+        if !polygons.is_empty() {
+            elements.push(Element::Path { polygons, color });
+        }
+    }
+    Scene {
+        view_box: (0f32, 0f32, grid_size, grid_size),
+        background: None,
+        clip_to_circle: false,
+        elements,
+    }
+}
+
+/// Calculate `png` image pixel data (only pixel colors)
+///
+/// Each pixel gets the color of the grid cell its center falls into.
+#[cfg(feature = "pix")]
+pub fn calculate_png_data(size_in_pixels: u32, blockies: &Blockies) -> Vec<u8> {
+    let size = size_in_pixels as usize;
+    let mut data: Vec<u8> = Vec::with_capacity(size * size * 4);
+    let cell = |i: usize| ((2 * i + 1) * GRID_SIZE / (2 * size)).min(GRID_SIZE - 1);
+    for j in 0..size {
+        for i in 0..size {
+            data.extend_from_slice(&blockies.cell_color(cell(j), cell(i)).to_array());
+        }
+    }
+    data
+}
+
+/// Calculate `svg` file contents
+///
+/// Grid cell is a unit square, background is a single square covering
+/// the whole grid, other cells are drawn over it.
+#[cfg(feature = "vec")]
+pub fn calculate_svg_data(blockies: &Blockies) -> Vec<element::Rectangle> {
+    let mut out: Vec<element::Rectangle> = Vec::new();
+    out.push(
+        element::Rectangle::new()
+            .set("x", 0)
+            .set("y", 0)
+            .set("width", GRID_SIZE)
+            .set("height", GRID_SIZE)
+            .set("fill", blockies.background.to_hex()),
+    );
+    for (row, cells) in blockies.grid.iter().enumerate() {
+        for (column, value) in cells.iter().enumerate() {
+            if *value != 0 {
+                out.push(
+                    element::Rectangle::new()
+                        .set("x", column)
+                        .set("y", row)
+                        .set("width", 1)
+                        .set("height", 1)
+                        .set("fill", blockies.cell_color(row, column).to_hex()),
+                );
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(red: u8, green: u8, blue: u8) -> Color {
+        Color {
+            red,
+            green,
+            blue,
+            alpha: 255,
+        }
+    }
+
+    fn grid(cells: &str) -> [[u8; GRID_SIZE]; GRID_SIZE] {
+        let mut grid = [[0u8; GRID_SIZE]; GRID_SIZE];
+        for (i, c) in cells.chars().enumerate() {
+            grid[i / GRID_SIZE][i % GRID_SIZE] = c.to_digit(10).unwrap() as u8;
+        }
+        grid
+    }
+
+    /// Reference values calculated with javascript blockies code
+    #[test]
+    fn reference_vectors() {
+        for (seed, expected) in [
+            (
+                "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac",
+                Blockies {
+                    color: rgb(168, 121, 27),
+                    background: rgb(154, 31, 107),
+                    spot_color: rgb(83, 225, 189),
+                    grid: grid("1112211100111100101111010100001011111111010000100121121021111112"),
+                },
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                Blockies {
+                    color: rgb(218, 69, 84),
+                    background: rgb(44, 118, 209),
+                    spot_color: rgb(200, 123, 165),
+                    grid: grid("0010010010000001011001102111111210122101100110012000000200211200"),
+                },
+            ),
+            (
+                "0xd43593c715fdd31c61141abd04a99fd6822c8558",
+                Blockies {
+                    color: rgb(189, 225, 171),
+                    background: rgb(251, 102, 22),
+                    spot_color: rgb(33, 172, 227),
+                    grid: grid("0110011002000020010110101110011101011010211001121122221101111110"),
+                },
+            ),
+        ] {
+            assert_eq!(get_blockies_from_seed(seed), expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn account_seed() {
+        let account = [
+            0xf2, 0x4f, 0xf3, 0xa9, 0xcf, 0x04, 0xc7, 0x1d, 0xbc, 0x94, 0xd0, 0xb5, 0x66, 0xf7,
+            0xa2, 0x7b, 0x94, 0x56, 0x6c, 0xac,
+        ];
+        assert_eq!(
+            seed_from_account(&account),
+            "0xf24ff3a9cf04c71dbc94d0b566f7a27b94566cac"
+        );
+        assert_eq!(
+            get_blockies(&account),
+            get_blockies_from_seed("0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac")
+        );
+    }
+}
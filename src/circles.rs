@@ -1,11 +1,15 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
 #[cfg(feature = "vec")]
 use svg::node::element;
 
-#[cfg(any(feature = "vec", feature = "pix"))]
 use crate::colors::Color;
+use crate::math;
+#[cfg(feature = "alloc")]
+use crate::scene::{Element, Scene};
+use crate::style::Style;
 
 /// Information about the circle
-#[cfg(feature = "pix")]
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct Circle {
     x_center: f32,
@@ -15,16 +19,132 @@ struct Circle {
 }
 
 /// Function to determine if the point (x, y) is within the circle
-#[cfg(feature = "pix")]
-fn in_circle(x: i32, y: i32, circle: &Circle) -> bool {
-    (x as f32 - circle.x_center).powi(2) + (y as f32 - circle.y_center).powi(2)
-        < circle.radius.powi(2)
+fn in_circle(x: f32, y: f32, circle: &Circle) -> bool {
+    let (dx, dy) = (x - circle.x_center, y - circle.y_center);
+    dx * dx + dy * dy < circle.radius * circle.radius
+}
+
+/// Information about the ring, i.e. circle outline
+///
+/// Ring is the band between `outer` and `inner` circles, of the same color,
+/// with center line `radius` and `width` as set in the scene.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ring {
+    outer: Circle,
+    inner: Circle,
+    radius: f32,
+    width: f32,
+}
+
+impl Ring {
+    fn new(x_center: f32, y_center: f32, radius: f32, width: f32, rgba_color: Color) -> Self {
+        let circle = |radius: f32| Circle {
+            x_center,
+            y_center,
+            radius,
+            rgba_color,
+        };
+        Self {
+            outer: circle(radius + width / 2f32),
+            inner: circle((radius - width / 2f32).max(0f32)),
+            radius,
+            width,
+        }
+    }
+
+    /// Ring as the scene element
+    #[cfg(feature = "alloc")]
+    fn element(&self) -> Element {
+        Element::Ring {
+            x_center: self.outer.x_center,
+            y_center: self.outer.y_center,
+            radius: self.radius,
+            width: self.width,
+            color: self.outer.rgba_color,
+        }
+    }
+
+    /// Function to determine if the point (x, y) is within the ring
+    fn contains(&self, x: f32, y: f32) -> bool {
+        in_circle(x, y, &self.outer) && !in_circle(x, y, &self.inner)
+    }
+
+    /// Function to calculate which part of the pixel area is covered by the ring
+    fn coverage(&self, x: f64, y: f64) -> f64 {
+        (pixel_coverage(x, y, &self.outer) - pixel_coverage(x, y, &self.inner)).max(0f64)
+    }
+}
+
+/// Point within each `png` pixel that is checked to be inside or outside of the circles
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Centering {
+    /// Pixel centers `(x + 0.5, y + 0.5)` are sampled, image center is exactly
+    /// in the middle of the image, and the image is perfectly symmetric for
+    /// both odd and even image sizes
+    #[default]
+    Exact,
+    /// Pixel corners are sampled, as in earlier versions of this crate;
+    /// images with even size are off-centered by a pixel
+    Legacy,
+}
+
+/// Area of the part of circle centered at `(0, 0)` with radius `r`,
+/// lying above the horizontal line `y = h` (with `h >= 0`)
+/// and between the vertical lines `x = x0` and `x = x1`
+fn area_above(x0: f64, x1: f64, h: f64, r: f64) -> f64 {
+    // half of the chord length at height `h`
+    let s = if h < r {
+        math::sqrt(r * r - h * h)
+    } else {
+        0f64
+    };
+    // antiderivative of chord height above line `y = h`
+    let g = |x: f64| {
+        0.5 * (x * math::sqrt((r * r - x * x).max(0f64)) + r * r * math::asin(x / r) - 2f64 * h * x)
+    };
+    g(x1.clamp(-s, s)) - g(x0.clamp(-s, s))
+}
+
+/// Area of intersection of circle centered at `(0, 0)` with radius `r`
+/// and box `[x0, x1] x [y0, y1]`
+fn area_in_box(x0: f64, x1: f64, y0: f64, y1: f64, r: f64) -> f64 {
+    if y0 < 0f64 {
+        if y1 < 0f64 {
+            // box is below the center, mirror it
+            area_in_box(x0, x1, -y1, -y0, r)
+        } else {
+            // box is crossing the horizontal line through the center, split it
+            area_in_box(x0, x1, 0f64, -y0, r) + area_in_box(x0, x1, 0f64, y1, r)
+        }
+    } else {
+        area_above(x0, x1, y0, r) - area_above(x0, x1, y1, r)
+    }
+}
+
+/// Function to calculate which part of the pixel area is covered by the circle
+///
+/// Pixel is a unit square with top left corner at point (x, y),
+/// in the same coordinates as the circle center.
+fn pixel_coverage(x: f64, y: f64, circle: &Circle) -> f64 {
+    let x0 = x - circle.x_center as f64;
+    let y0 = y - circle.y_center as f64;
+    let r = circle.radius as f64;
+    if x0 >= r || y0 >= r || x0 + 1f64 <= -r || y0 + 1f64 <= -r {
+        return 0f64;
+    }
+    let max_x = x0.abs().max((x0 + 1f64).abs());
+    let max_y = y0.abs().max((y0 + 1f64).abs());
+    if max_x * max_x + max_y * max_y <= r * r {
+        return 1f64;
+    }
+    area_in_box(x0, x0 + 1f64, y0, y0 + 1f64, r).clamp(0f64, 1f64)
 }
 
 /// Information about circle center position
 ///
 /// `position_circle_set` sets default positions for small circles in 19-circles icon
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CirclePosition {
     pub x_center: f32,
     pub y_center: f32,
@@ -35,7 +155,7 @@ pub struct CirclePosition {
 /// Input is `f32` center-to-center distance between small circles
 pub fn position_circle_set(center_to_center: f32) -> [CirclePosition; 19] {
     let a = center_to_center;
-    let b = center_to_center * 3f32.sqrt() / 2f32;
+    let b = center_to_center * math::sqrtf(3f32) / 2f32;
     [
         CirclePosition {
             x_center: 0f32,
@@ -116,101 +236,691 @@ pub fn position_circle_set(center_to_center: f32) -> [CirclePosition; 19] {
     ]
 }
 
-/// Function to finalize 19 circles with properly corresponding colors and radius
-#[cfg(feature = "pix")]
-fn get_colored_circles(
-    center_to_center: f32,
-    small_radius: f32,
-    colors: [Color; 19],
-) -> [Circle; 19] {
+/// Ring of the small circle with given index in [`position_circle_set`]:
+/// `0` for the central circle, `1` for the inner ring of 6 circles,
+/// `2` for the outer ring of 12 circles
+///
+/// # Panics
+///
+/// Panics if the index is not below 19.
+pub fn circle_ring(index: usize) -> u8 {
+    let position = position_circle_set(1f32)[index];
+    let distance = math::hypotf(position.x_center, position.y_center);
+    if distance < 0.5 {
+        0
+    } else if distance < 1.5 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Layers of the identicon, from bottom to top
+///
+/// Fixed-size, so that the identicons could be drawn without allocations.
+struct Layers {
+    /// Selection ring, under the outer circle
+    selection: Option<Ring>,
+    big_circle: Circle,
+    small_circles: [Circle; 19],
+    /// Outline of the outer circle, over the small circles
+    outline: Option<Ring>,
+    /// Strokes of the small circles, over the outline
+    strokes: Option<[Ring; 19]>,
+}
+
+impl Layers {
+    /// Rings over the small circles, in layer order
+    fn over(&self) -> impl Iterator<Item = &Ring> {
+        self.outline.iter().chain(self.strokes.iter().flatten())
+    }
+}
+
+/// Calculate identicon [`Layers`], with colors and decorations set in the
+/// [`Style`]
+///
+/// Geometry is described in [`calculate_scene_with_style`], scene elements
+/// are made from the layers.
+fn layers_with_style(big_radius: f32, colors: [Color; 19], style: &Style) -> Layers {
+    let size = 2f32 * big_radius;
+    let circle = |x_center: f32, y_center: f32, radius: f32, rgba_color: Color| Circle {
+        x_center,
+        y_center,
+        radius,
+        rgba_color,
+    };
+    let (selection, disc_radius) = match style.selection {
+        Some(selection) => {
+            let width = selection.width * size;
+            (
+                Some(Ring::new(
+                    0f32,
+                    0f32,
+                    big_radius - width / 2f32,
+                    width,
+                    selection.color,
+                )),
+                (big_radius - width - selection.gap * size).max(0f32),
+            )
+        }
+        None => (None, big_radius),
+    };
+    let small_radius = disc_radius / 32f32 * 5f32;
+    let center_to_center = disc_radius / 8f32 * 3f32;
     let positions = position_circle_set(center_to_center);
-    let mut out: Vec<Circle> = Vec::with_capacity(19);
-    for (i, position) in positions.iter().enumerate() {
-        let new = Circle {
-            x_center: position.x_center,
-            y_center: position.y_center,
-            radius: small_radius,
-            rgba_color: colors[i],
-        };
-        out.push(new);
+    // outline is inside the disc, so that it fits into the image
+    let outline = style.outline.map(|outline| {
+        let width = (outline.width * size).min(disc_radius);
+        Ring::new(0f32, 0f32, disc_radius - width / 2f32, width, outline.color)
+    });
+    let strokes = style.circle_stroke.map(|stroke| {
+        positions.map(|position| {
+            Ring::new(
+                position.x_center,
+                position.y_center,
+                small_radius,
+                stroke.width * size,
+                stroke.color,
+            )
+        })
+    });
+    Layers {
+        selection,
+        big_circle: circle(0f32, 0f32, disc_radius, style.disc),
+        small_circles: core::array::from_fn(|i| {
+            circle(
+                positions[i].x_center,
+                positions[i].y_center,
+                small_radius,
+                colors[i],
+            )
+        }),
+        outline,
+        strokes,
     }
-    out.try_into().expect("always generate 19-element set")
 }
 
 /// Calculate `png` image pixel data (only pixel colors)
 ///
-/// Iterates over all `png` image pixels and sets the color.
+/// Iterates over all `png` image pixels and sets the color,
+/// pixel centers are sampled (see [`Centering::Exact`]).
 ///
 /// Requires image size in pixels (equal to diameter of largest, outer circle),
 /// and identicon colors
-#[cfg(feature = "pix")]
-pub fn calculate_png_data(size_in_pixels: u16, colors: [Color; 19]) -> Vec<u8> {
-    let mut data: Vec<u8> = Vec::new();
-    let big_radius = size_in_pixels as f32 / 2f32;
-    let small_radius = big_radius / 32f32 * 5f32;
-    let center_to_center = big_radius / 8f32 * 3f32;
-
-    let big_circle = Circle {
-        x_center: 0f32,
-        y_center: 0f32,
-        radius: big_radius,
-        rgba_color: Color::foreground(),
-    };
+#[cfg(feature = "alloc")]
+pub fn calculate_png_data(size_in_pixels: u32, colors: [Color; 19]) -> Vec<u8> {
+    calculate_png_data_with_centering(size_in_pixels, colors, Centering::Exact)
+}
 
-    let small_circles_set = get_colored_circles(center_to_center, small_radius, colors);
+/// Calculate `png` image pixel data (only pixel colors), with colors and
+/// decorations set in the [`Style`]
+///
+/// Pixel centers are sampled (see [`Centering::Exact`]).
+#[cfg(feature = "alloc")]
+pub fn calculate_png_data_with_style(
+    size_in_pixels: u32,
+    colors: [Color; 19],
+    style: &Style,
+) -> Vec<u8> {
+    let mut data = vec![0u8; rgba_len(size_in_pixels)];
+    write_png_data_with_style(
+        size_in_pixels,
+        colors,
+        Centering::Exact,
+        style,
+        &mut data,
+        size_in_pixels as usize * 4,
+    );
+    data
+}
 
-    let iter_start = -(size_in_pixels as i32) / 2;
-    let iter_end = { (size_in_pixels >> 1) + (size_in_pixels & 0x01) } as i32;
+/// Calculate `png` image pixel data (only pixel colors), with selected
+/// [`Centering`]
+#[cfg(feature = "alloc")]
+pub fn calculate_png_data_with_centering(
+    size_in_pixels: u32,
+    colors: [Color; 19],
+    centering: Centering,
+) -> Vec<u8> {
+    let mut data = vec![0u8; rgba_len(size_in_pixels)];
+    write_png_data(
+        size_in_pixels,
+        colors,
+        centering,
+        &mut data,
+        size_in_pixels as usize * 4,
+    );
+    data
+}
+
+/// Write `png` image pixel data (only pixel colors) into RGBA8 buffer
+///
+/// Iterates over all `png` image pixels and sets the color.
+///
+/// Requires image size in pixels (equal to diameter of largest, outer circle),
+/// identicon colors, [`Centering`], and the buffer with its stride,
+/// i.e. number of bytes between starts of consecutive pixel rows.
+///
+/// # Panics
+///
+/// Panics if the buffer could not fit the image with given stride.
+pub fn write_png_data(
+    size_in_pixels: u32,
+    colors: [Color; 19],
+    centering: Centering,
+    data: &mut [u8],
+    stride: usize,
+) {
+    write_png_data_with_style(
+        size_in_pixels,
+        colors,
+        centering,
+        &Style::default(),
+        data,
+        stride,
+    )
+}
+
+/// Write `png` image pixel data (only pixel colors) into RGBA8 buffer, with
+/// colors and decorations set in the [`Style`]
+///
+/// # Panics
+///
+/// Panics if the buffer could not fit the image with given stride.
+pub fn write_png_data_with_style(
+    size_in_pixels: u32,
+    colors: [Color; 19],
+    centering: Centering,
+    style: &Style,
+    data: &mut [u8],
+    stride: usize,
+) {
+    let big_radius = size_in_pixels as f32 / 2f32;
+    let layers = layers_with_style(big_radius, colors, style);
+
+    // coordinate of the sampled point in the first pixel row or column
+    let start = match centering {
+        Centering::Exact => 0.5f32 - big_radius,
+        Centering::Legacy => (-(size_in_pixels as i64) / 2) as f32,
+    };
 
     // calculating color for each pixel
-    for y in iter_start..iter_end {
-        for x in iter_start..iter_end {
-            if in_circle(x, y, &big_circle) {
+    for j in 0..size_in_pixels as usize {
+        let y = start + j as f32;
+        for i in 0..size_in_pixels as usize {
+            let x = start + i as f32;
+            let mut color = style.background;
+            for ring in layers.selection.iter() {
+                if ring.contains(x, y) {
+                    color = ring.outer.rgba_color;
+                }
+            }
+            if in_circle(x, y, &layers.big_circle) {
                 let mut some_small_circle = None;
-                for cir in small_circles_set.iter() {
+                for cir in layers.small_circles.iter() {
                     if in_circle(x, y, cir) {
                         some_small_circle = Some(cir.rgba_color);
                         break;
                     }
                 }
-                match some_small_circle {
-                    Some(color) => data.extend_from_slice(&color.to_array()),
-                    None => data.extend_from_slice(&big_circle.rgba_color.to_array()),
+                color = match some_small_circle {
+                    Some(color) => color,
+                    None => layers.big_circle.rgba_color,
+                };
+            }
+            for ring in layers.over() {
+                if ring.contains(x, y) {
+                    color = ring.outer.rgba_color;
                 }
-            } else {
-                data.extend_from_slice(&Color::background().to_array())
             }
+            let start = j * stride + i * 4;
+            data[start..start + 4].copy_from_slice(&color.to_array());
         }
     }
+    #[cfg(feature = "alloc")]
+    if let Some(ref badge) = style.badge {
+        let scene = calculate_scene_with_style(big_radius, colors, style);
+        crate::badge::draw_png_data(badge, &scene, size_in_pixels, data, stride);
+    }
+}
+
+/// Calculate anti-aliased `png` image pixel data (only pixel colors)
+///
+/// For each `png` image pixel, the exact area covered by each circle is calculated,
+/// and the circle colors are composited with corresponding alpha:
+/// large, outer circle over the background, and small circles over the large one.
+/// Circle edges are smooth without any additional image resizing.
+///
+/// Image center is exactly in the middle of the image,
+/// both for odd and for even image sizes.
+///
+/// Requires image size in pixels (equal to diameter of largest, outer circle),
+/// and identicon colors
+#[cfg(feature = "alloc")]
+pub fn calculate_png_data_antialiased(size_in_pixels: u32, colors: [Color; 19]) -> Vec<u8> {
+    calculate_png_data_antialiased_with_style(size_in_pixels, colors, &Style::default())
+}
+
+/// Calculate anti-aliased `png` image pixel data (only pixel colors), with
+/// colors and decorations set in the [`Style`]
+///
+/// Large circle is composited over the background color.
+#[cfg(feature = "alloc")]
+pub fn calculate_png_data_antialiased_with_style(
+    size_in_pixels: u32,
+    colors: [Color; 19],
+    style: &Style,
+) -> Vec<u8> {
+    let mut data = vec![0u8; rgba_len(size_in_pixels)];
+    write_png_data_antialiased_with_style(
+        size_in_pixels,
+        colors,
+        style,
+        &mut data,
+        size_in_pixels as usize * 4,
+    );
     data
 }
 
+/// Write anti-aliased `png` image pixel data (only pixel colors) into RGBA8 buffer
+///
+/// Same as [`calculate_png_data_antialiased`], but with caller-supplied
+/// buffer and its stride, i.e. number of bytes between starts of consecutive
+/// pixel rows.
+///
+/// # Panics
+///
+/// Panics if the buffer could not fit the image with given stride.
+pub fn write_png_data_antialiased(
+    size_in_pixels: u32,
+    colors: [Color; 19],
+    data: &mut [u8],
+    stride: usize,
+) {
+    write_png_data_antialiased_with_style(size_in_pixels, colors, &Style::default(), data, stride)
+}
+
+/// Write anti-aliased `png` image pixel data (only pixel colors) into RGBA8
+/// buffer, with colors and decorations set in the [`Style`]
+///
+/// # Panics
+///
+/// Panics if the buffer could not fit the image with given stride.
+pub fn write_png_data_antialiased_with_style(
+    size_in_pixels: u32,
+    colors: [Color; 19],
+    style: &Style,
+    data: &mut [u8],
+    stride: usize,
+) {
+    let big_radius = size_in_pixels as f32 / 2f32;
+    let layers = layers_with_style(big_radius, colors, style);
+    let background = premultiplied(&style.background, 1f64);
+
+    // calculating color for each pixel
+    for j in 0..size_in_pixels as usize {
+        for i in 0..size_in_pixels as usize {
+            let x = i as f64 - big_radius as f64;
+            let y = j as f64 - big_radius as f64;
+            // premultiplied color components, start with the background
+            let mut pixel = background;
+            for ring in layers.selection.iter() {
+                let coverage = ring.coverage(x, y);
+                if coverage > 0f64 {
+                    over(&mut pixel, premultiplied(&ring.outer.rgba_color, coverage));
+                }
+            }
+            // large circle over the background
+            let coverage = pixel_coverage(x, y, &layers.big_circle);
+            if coverage > 0f64 {
+                over(
+                    &mut pixel,
+                    premultiplied(&layers.big_circle.rgba_color, coverage),
+                );
+            }
+            // small circles over the large one; small circles do not overlap,
+            // so within a pixel their covered areas are disjoint and could be
+            // simply added up, independent of the order
+            let mut small = [0f64; 4];
+            for circle in layers.small_circles.iter() {
+                let coverage = pixel_coverage(x, y, circle);
+                if coverage > 0f64 {
+                    let color = premultiplied(&circle.rgba_color, coverage);
+                    for k in 0..4 {
+                        small[k] += color[k];
+                    }
+                }
+            }
+            over(&mut pixel, small);
+            for ring in layers.over() {
+                let coverage = ring.coverage(x, y);
+                if coverage > 0f64 {
+                    over(&mut pixel, premultiplied(&ring.outer.rgba_color, coverage));
+                }
+            }
+            let start = j * stride + i * 4;
+            if pixel[3] > 0f64 {
+                for k in 0..3 {
+                    data[start + k] = math::round(pixel[k] / pixel[3] * 255f64) as u8;
+                }
+                data[start + 3] = math::round(pixel[3] * 255f64) as u8;
+            } else {
+                data[start..start + 4].copy_from_slice(&style.background.to_array())
+            }
+        }
+    }
+    #[cfg(feature = "alloc")]
+    if let Some(ref badge) = style.badge {
+        let scene = calculate_scene_with_style(big_radius, colors, style);
+        crate::badge::draw_png_data(badge, &scene, size_in_pixels, data, stride);
+    }
+}
+
+/// Composite premultiplied color over the premultiplied pixel
+fn over(pixel: &mut [f64; 4], color: [f64; 4]) {
+    for k in 0..4 {
+        pixel[k] = color[k] + pixel[k] * (1f64 - color[3]);
+    }
+}
+
+/// Premultiplied color components, for the color covering given part of the pixel
+fn premultiplied(color: &Color, coverage: f64) -> [f64; 4] {
+    let alpha = coverage * color.alpha as f64 / 255f64;
+    [
+        color.red as f64 / 255f64 * alpha,
+        color.green as f64 / 255f64 * alpha,
+        color.blue as f64 / 255f64 * alpha,
+        alpha,
+    ]
+}
+
+/// Number of bytes in RGBA8 pixel data of square image, with no row padding
+#[cfg(feature = "alloc")]
+fn rgba_len(size_in_pixels: u32) -> usize {
+    size_in_pixels as usize * size_in_pixels as usize * 4
+}
+
+/// Calculate [`Scene`]: outer circle and 19 small circles over it
+///
+/// Inputs radius of outer circle (largest one) and identicon colors,
+/// outer circle is centered at `(0, 0)`. Both `png` and `svg` identicons
+/// are made from this scene.
+#[cfg(feature = "alloc")]
+pub fn calculate_scene(big_radius: f32, colors: [Color; 19]) -> Scene {
+    calculate_scene_with_style(big_radius, colors, &Style::default())
+}
+
+/// Calculate [`Scene`], with colors and decorations set in the [`Style`]
+///
+/// Inputs half of the image size, identicon colors and style. Without
+/// selection ring the outer circle radius is `big_radius`, selection ring is
+/// placed along the image edge and the circles are shrunk to fit within it.
+/// Selection ring is the first scene element, outline and small circle
+/// strokes are the last.
+#[cfg(feature = "alloc")]
+pub fn calculate_scene_with_style(big_radius: f32, colors: [Color; 19], style: &Style) -> Scene {
+    let layers = layers_with_style(big_radius, colors, style);
+    let mut elements: Vec<Element> = Vec::with_capacity(20);
+    elements.extend(layers.selection.map(|ring| ring.element()));
+    for circle in core::iter::once(&layers.big_circle).chain(layers.small_circles.iter()) {
+        elements.push(Element::Circle {
+            x_center: circle.x_center,
+            y_center: circle.y_center,
+            radius: circle.radius,
+            color: circle.rgba_color,
+        });
+    }
+    elements.extend(layers.over().map(|ring| ring.element()));
+    Scene {
+        view_box: (
+            -big_radius,
+            -big_radius,
+            2f32 * big_radius,
+            2f32 * big_radius,
+        ),
+        background: Some(style.background),
+        clip_to_circle: false,
+        elements,
+    }
+}
+
 /// Calculate `svg` file contents
 ///
 /// Inputs radius of outer circle (largest one) and identicon colors
 #[cfg(feature = "vec")]
 pub fn calculate_svg_data(big_radius: f32, colors: [Color; 19]) -> Vec<element::Circle> {
-    let mut out: Vec<element::Circle> = Vec::with_capacity(20);
-    out.push(
-        element::Circle::new()
-            .set("cx", 0f32)
-            .set("cy", 0f32)
-            .set("r", big_radius)
-            .set("fill", Color::foreground().to_hex())
-            .set("stroke", "none"),
-    );
-    let small_radius = big_radius / 32f32 * 5f32;
-    let center_to_center = big_radius / 8f32 * 3f32;
-    let positions = position_circle_set(center_to_center);
-    for (i, position) in positions.iter().enumerate() {
-        out.push(
-            element::Circle::new()
-                .set("cx", position.x_center)
-                .set("cy", position.y_center)
-                .set("r", small_radius)
-                .set("fill", colors[i].to_hex())
-                .set("stroke", "none"),
+    calculate_svg_data_with_style(big_radius, colors, &Style::default())
+}
+
+/// Calculate `svg` file contents, with disc color and decorations set in
+/// the [`Style`]
+///
+/// Circles are followed by decoration rings, as `svg` circles with stroke
+/// and no fill; selection ring goes first. Background is not included, it
+/// is drawn by the document, see
+/// [`crate::generate_svg_with_style`].
+#[cfg(feature = "vec")]
+pub fn calculate_svg_data_with_style(
+    big_radius: f32,
+    colors: [Color; 19],
+    style: &Style,
+) -> Vec<element::Circle> {
+    calculate_scene_with_style(big_radius, colors, style)
+        .elements
+        .iter()
+        .filter_map(crate::scene::svg_circle)
+        .collect()
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coverage_adds_up_to_circle_area() {
+        for (x_center, y_center, radius) in [(0f32, 0f32, 5f32), (0.3, -1.7, 7.25), (2.5, 2.5, 0.4)]
+        {
+            let circle = Circle {
+                x_center,
+                y_center,
+                radius,
+                rgba_color: Color::foreground(),
+            };
+            let mut total = 0f64;
+            for y in -10..10 {
+                for x in -10..10 {
+                    total += pixel_coverage(x as f64, y as f64, &circle);
+                }
+            }
+            let expected = std::f64::consts::PI * (radius as f64).powi(2);
+            assert!(
+                (total - expected).abs() < 1e-6,
+                "got {}, expected {}",
+                total,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn coverage_of_pixel_on_edge() {
+        let circle = Circle {
+            x_center: 0f32,
+            y_center: 0f32,
+            radius: 1f32,
+            rgba_color: Color::foreground(),
+        };
+        // quarter of unit circle
+        let coverage = pixel_coverage(0f64, 0f64, &circle);
+        assert!((coverage - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scene_geometry() {
+        let colors = crate::colors::get_colors(&[0]);
+        let scene = calculate_scene(16f32, colors);
+        assert_eq!(scene.view_box, (-16f32, -16f32, 32f32, 32f32));
+        assert_eq!(scene.elements.len(), 20);
+        let layers = layers_with_style(16f32, colors, &Style::default());
+        assert!(layers.selection.is_none() && layers.over().next().is_none());
+        let Layers {
+            big_circle,
+            small_circles,
+            ..
+        } = layers;
+        assert_eq!(big_circle.radius, 16f32);
+        assert_eq!(big_circle.rgba_color, Color::foreground());
+        assert_eq!(small_circles.len(), 19);
+        for (circle, color) in small_circles.iter().zip(colors.iter()) {
+            assert_eq!(circle.radius, 2.5f32);
+            assert_eq!(circle.rgba_color, *color);
+        }
+        // central circle is the last one
+        assert_eq!(
+            (small_circles[18].x_center, small_circles[18].y_center),
+            (0f32, 0f32)
+        );
+        assert_eq!(small_circles[0].y_center, -12f32);
+    }
+
+    #[test]
+    fn selection_shrinks_circles() {
+        let style = Style {
+            selection: Some(crate::style::Selection {
+                width: 0.125,
+                gap: 0.125,
+                color: Color::foreground(),
+            }),
+            outline: Some(crate::style::Stroke {
+                width: 0.0625,
+                color: Color::foreground(),
+            }),
+            ..Style::default()
+        };
+        let colors = crate::colors::get_colors(&[0]);
+        let scene = calculate_scene_with_style(16f32, colors, &style);
+        assert_eq!(scene.view_box, (-16f32, -16f32, 32f32, 32f32));
+        assert_eq!(scene.elements.len(), 22);
+        let layers = layers_with_style(16f32, colors, &style);
+        let selection = layers.selection.unwrap();
+        assert_eq!(selection.outer.radius, 16f32);
+        assert_eq!(selection.inner.radius, 12f32);
+        assert_eq!(scene.elements[0], selection.element());
+        assert_eq!(layers.big_circle.radius, 8f32);
+        assert_eq!(layers.small_circles[0].radius, 1.25f32);
+        let over: Vec<&Ring> = layers.over().collect();
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].outer.radius, 8f32);
+        assert_eq!(over[0].inner.radius, 6f32);
+    }
+
+    #[test]
+    fn antialiased_edges_are_smooth() {
+        let colors = [Color {
+            red: 200,
+            green: 0,
+            blue: 0,
+            alpha: 255,
+        }; 19];
+        let size = 16;
+        let data = calculate_png_data_antialiased(size, colors);
+        assert_eq!(data.len(), size as usize * size as usize * 4);
+        // corner pixel is outside of the large circle
+        assert_eq!(data[0..4], Color::background().to_array());
+        // central pixels are fully opaque
+        let center = ((size as usize / 2) * size as usize + size as usize / 2) * 4;
+        assert_eq!(data[center + 3], 255);
+        // there are partially transparent pixels on the large circle edge
+        assert!(data.chunks(4).any(|pixel| pixel[3] > 0 && pixel[3] < 255));
+    }
+
+    /// Colors for 19 circles following the coloring scheme, with no rotation,
+    /// each palette element gets its own distinct color
+    fn scheme_colors(scheme: usize) -> [Color; 19] {
+        let schemes = crate::colors::default_schemes();
+        schemes[scheme].colors.map(|palette_index| Color {
+            red: (palette_index * 40) as u8,
+            green: 255 - (palette_index * 40) as u8,
+            blue: 100,
+            alpha: 255,
+        })
+    }
+
+    /// Check that pixel data is symmetric with respect to both
+    /// vertical and horizontal axes through the image center
+    fn assert_mirror_symmetric(data: &[u8], size: usize) {
+        let pixel = |i: usize, j: usize| &data[(j * size + i) * 4..(j * size + i + 1) * 4];
+        for j in 0..size {
+            for i in 0..size {
+                assert_eq!(
+                    pixel(i, j),
+                    pixel(size - 1 - i, j),
+                    "vertical axis asymmetry at ({}, {}) for size {}",
+                    i,
+                    j,
+                    size
+                );
+                assert_eq!(
+                    pixel(i, j),
+                    pixel(i, size - 1 - j),
+                    "horizontal axis asymmetry at ({}, {}) for size {}",
+                    i,
+                    j,
+                    size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn target_and_flower_are_symmetric() {
+        // "target" and "flower" coloring schemes
+        for scheme in [0, 3] {
+            let colors = scheme_colors(scheme);
+            for size in [15u32, 16, 30, 31, 64, 150] {
+                assert_mirror_symmetric(&calculate_png_data(size, colors), size as usize);
+                assert_mirror_symmetric(
+                    &calculate_png_data_antialiased(size, colors),
+                    size as usize,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn legacy_centering_is_off_center_for_even_sizes() {
+        let colors = scheme_colors(0);
+        let data = calculate_png_data_with_centering(16, colors, Centering::Legacy);
+        // leftmost column is cut, while the rightmost one still has the large circle
+        let column_is_empty = |i: usize| (0..16).all(|j| data[(j * 16 + i) * 4 + 3] == 0);
+        assert!(column_is_empty(0));
+        assert!(!column_is_empty(15));
+        // odd sizes are rendered identically in both modes
+        assert_eq!(
+            calculate_png_data_with_centering(15, colors, Centering::Legacy),
+            calculate_png_data(15, colors)
         );
     }
-    out
+
+    #[test]
+    fn strided_write_matches_packed_data() {
+        let colors = [Color::foreground(); 19];
+        let size = 11u32;
+        let stride = size as usize * 4 + 6;
+        let packed = calculate_png_data(size, colors);
+        let mut strided = vec![7u8; stride * size as usize];
+        write_png_data(size, colors, Centering::Exact, &mut strided, stride);
+        for row in 0..size as usize {
+            let packed_row = &packed[row * size as usize * 4..(row + 1) * size as usize * 4];
+            assert_eq!(
+                &strided[row * stride..row * stride + size as usize * 4],
+                packed_row
+            );
+            // padding bytes are left untouched
+            assert_eq!(
+                &strided[row * stride + size as usize * 4..(row + 1) * stride],
+                &[7u8; 6]
+            );
+        }
+    }
 }
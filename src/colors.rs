@@ -1,48 +1,119 @@
 use blake2_rfc::blake2b::blake2b;
-use palette::{FromColor, FromComponent, Hsl, RgbHue, Srgb};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::style::Style;
+
+/// Coloring scheme, as named in js code
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Scheme {
+    Target,
+    Cube,
+    Quazar,
+    Flower,
+    Cyclic,
+    VMirror,
+    HMirror,
+}
+
+impl Scheme {
+    /// Scheme name, as in js code
+    pub fn name(&self) -> &'static str {
+        match &self {
+            Scheme::Target => "target",
+            Scheme::Cube => "cube",
+            Scheme::Quazar => "quazar",
+            Scheme::Flower => "flower",
+            Scheme::Cyclic => "cyclic",
+            Scheme::VMirror => "vmirror",
+            Scheme::HMirror => "hmirror",
+        }
+    }
+}
+
+/// Conversion of the palette colors from HSL into RGB
+///
+/// Palette colors are set in js code as `hsl()` CSS colors, with saturation
+/// from 30% to 109%. Browsers clamp saturation to 100%, Rust conversion in
+/// `palette` crate does not, and the colors differ for saturation over 100%.
+/// Components that are exact halves could also be rounded differently.
+/// Palette byte `0` gives dark grey `#444` in js code, and near-black
+/// `#040404` in this crate.
+///
+/// Default is [`ColorMode::Palette`], so that the identicons stay same as
+/// in earlier versions of this crate and in Signer.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{colors::{get_colors_with_style, ColorMode}, Style};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let js = Style {
+///     color_mode: ColorMode::Js,
+///     ..Style::default()
+/// };
+/// // Alice saturation is 56%, both modes give same colors
+/// assert_eq!(get_colors_with_style(alice, &js), get_colors_with_style(alice, &Style::default()));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ColorMode {
+    /// Same as `palette` crate conversion, saturation over 100% is not
+    /// clamped; resulting RGB components are clamped
+    #[default]
+    Palette,
+    /// Same as browser `hsl()` CSS function in polkadot.js identicons,
+    /// saturation over 100% is clamped; palette byte `0` is `#444`
+    Js,
+}
 
 /// Struct to store default coloring schemes
-struct SchemeElement {
-    freq: u8,
-    colors: [usize; 19],
+pub(crate) struct SchemeElement {
+    pub(crate) scheme: Scheme,
+    pub(crate) freq: u8,
+    pub(crate) colors: [usize; 19],
 }
 
 /// Function to set default coloring schemes, taken as is from js code
 #[rustfmt::skip]
-fn default_schemes() -> [SchemeElement; 7] {
+pub(crate) fn default_schemes() -> [SchemeElement; 7] {
     [
         SchemeElement {
-            // "target"
+            scheme: Scheme::Target,
             freq: 1,
             colors: [0, 28, 0, 0, 28, 0, 0, 28, 0, 0, 28, 0, 0, 28, 0, 0, 28, 0, 1],
         },
         SchemeElement {
-            // "cube",
+            scheme: Scheme::Cube,
             freq: 20,
             colors: [0, 1, 3, 2, 4, 3, 0, 1, 3, 2, 4, 3, 0, 1, 3, 2, 4, 3, 5],
         },
         SchemeElement {
-            // "quazar",
+            scheme: Scheme::Quazar,
             freq: 16,
             colors: [1, 2, 3, 1, 2, 4, 5, 5, 4, 1, 2, 3, 1, 2, 4, 5, 5, 4, 0],
         },
         SchemeElement {
-            // "flower",
+            scheme: Scheme::Flower,
             freq: 32,
             colors: [0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 3],
         },
         SchemeElement {
-            // "cyclic",
+            scheme: Scheme::Cyclic,
             freq: 32,
             colors: [0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6],
         },
         SchemeElement {
-            // "vmirror",
+            scheme: Scheme::VMirror,
             freq: 128,
             colors: [0, 1, 2, 3, 4, 5, 3, 4, 2, 0, 1, 6, 7, 8, 9, 7, 8, 6, 10],
         },
         SchemeElement {
-            // "hmirror",
+            scheme: Scheme::HMirror,
             freq: 128,
             colors: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 8, 6, 7, 5, 3, 4, 2, 11],
         },
@@ -60,7 +131,6 @@ pub struct Color {
 
 impl Color {
     /// convert `Color` into `[u8; 4]` array, for `png` image pixel-by-pixel generation
-    #[cfg(feature = "pix")]
     pub fn to_array(&self) -> [u8; 4] {
         [self.red, self.green, self.blue, self.alpha]
     }
@@ -71,8 +141,7 @@ impl Color {
         format!("#{}", hex::encode([self.red, self.green, self.blue]))
     }
 
-    /// set `Color` to default background, needed only for `png` images
-    #[cfg(feature = "pix")]
+    /// set `Color` to default background, transparent
     pub fn background() -> Self {
         Self {
             red: 255,
@@ -92,17 +161,15 @@ impl Color {
         }
     }
 
-    /// function to derive color from `u8` number and saturation component
+    /// function to derive color from `u8` number and saturation in percents
     /// calculated elsewhere;
     /// is accessible and used only for `u8` numbers other than 0 and 255;
     /// no check here is done for b value;
-    fn derive(b: u8, sat_component: f64) -> Self {
+    fn derive(b: u8, saturation: u8, mode: ColorMode) -> Self {
         // HSL color hue in degrees
         // calculated as integer, same as in js code
         // transformation to u16 is done to avoid overflow
         let h = (b as u16 % 64 * 360) / 64;
-        // recalculated into `RgbHue`, to be used as HSL hue component
-        let h_component = RgbHue::from_degrees(h as f64);
 
         // HSL lightness in percents
         let l: u8 = match b / 64 {
@@ -111,19 +178,13 @@ impl Color {
             2 => 35,
             _ => 75,
         };
-        // recalculated in HSL lightness component (component range is 0.00 to 1.00)
-        let l_component: f64 = (l as f64) / 100f64;
-
-        // defining HSL color
-        let color_hsl = Hsl::new(h_component, sat_component, l_component);
-
-        // transforming HSL color into RGB color, possibly lossy, TODO check if too lossy
-        let color_srgb = Srgb::from_color(color_hsl);
 
-        // getting red, green, blue components, transforming them in 0..255 range of u8
-        let red = u8::from_component(color_srgb.red);
-        let green = u8::from_component(color_srgb.green);
-        let blue = u8::from_component(color_srgb.blue);
+        // transforming HSL color into RGB color, with `palette` or with
+        // fixed-point arithmetic, both give the same result
+        #[cfg(all(feature = "palette", not(feature = "fixed_point")))]
+        let [red, green, blue] = crate::hsl::to_rgb_float(h, saturation, l, mode);
+        #[cfg(any(feature = "fixed_point", not(feature = "palette")))]
+        let [red, green, blue] = crate::hsl::to_rgb_fixed(h, saturation, l, mode);
 
         // finalize color, set alpha value to 255
         Self {
@@ -135,48 +196,252 @@ impl Color {
     }
 }
 
+/// Colors are serialized as `#rrggbbaa` strings in human-readable formats,
+/// and as 4 raw bytes otherwise
+#[cfg(feature = "serde")]
+impl Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                self.red, self.green, self.blue, self.alpha
+            ))
+        } else {
+            serializer.serialize_bytes(&[self.red, self.green, self.blue, self.alpha])
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ColorVisitor)
+        } else {
+            deserializer.deserialize_bytes(ColorVisitor)
+        }
+    }
+}
+
+/// Visitor for [`Color`] deserialization, from `#rrggbbaa` string or 4 bytes
+#[cfg(feature = "serde")]
+struct ColorVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "color as #rrggbbaa string or 4 bytes")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Color, E> {
+        let invalid = || E::invalid_value(serde::de::Unexpected::Str(v), &self);
+        let digits = v.strip_prefix('#').ok_or_else(invalid)?;
+        if digits.len() != 8 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let component = |i: usize| u8::from_str_radix(&digits[2 * i..2 * i + 2], 16);
+        match (component(0), component(1), component(2), component(3)) {
+            (Ok(red), Ok(green), Ok(blue), Ok(alpha)) => Ok(Color {
+                red,
+                green,
+                blue,
+                alpha,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Color, E> {
+        match v {
+            [red, green, blue, alpha] => Ok(Color {
+                red: *red,
+                green: *green,
+                blue: *blue,
+                alpha: *alpha,
+            }),
+            _ => Err(E::invalid_length(v.len(), &self)),
+        }
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Color, A::Error> {
+        let mut bytes = [0u8; 4];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(serde::de::Error::invalid_length(5, &self));
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+/// Identicon colors together with the coloring scheme they were picked by
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdenticonColors {
+    /// Coloring scheme
+    pub scheme: Scheme,
+    /// Rotation of the coloring scheme, number of outer ring circles
+    /// the scheme is shifted by; always one of 0, 3, 6, 9, 12, 15
+    pub rotation: u8,
+    /// Colors of 19 small circles, in [`crate::circles::position_circle_set`] order
+    pub colors: [Color; 19],
+}
+
 /// Function to calculate identicon colors from `&[u8]` input slice.
 /// Total 19 colors are always produced.
 pub fn get_colors(into_id: &[u8]) -> [Color; 19] {
+    get_identicon_colors(into_id).colors
+}
+
+/// Function to calculate identicon colors from `&[u8]` input slice, with
+/// blank circles (palette byte `255`) colored as set in the [`Style`].
+/// Total 19 colors are always produced.
+pub fn get_colors_with_style(into_id: &[u8], style: &Style) -> [Color; 19] {
+    get_identicon_info_with_style(into_id, style).colors
+}
+
+/// Function to calculate identicon colors from `&[u8]` input slice,
+/// with the coloring scheme and its rotation.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::colors::{get_colors, get_identicon_colors, Scheme};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let identicon_colors = get_identicon_colors(alice);
+/// assert_eq!(identicon_colors.colors, get_colors(alice));
+/// assert_eq!(identicon_colors.scheme.name(), "vmirror");
+/// ```
+pub fn get_identicon_colors(into_id: &[u8]) -> IdenticonColors {
+    let info = get_identicon_info(into_id);
+    IdenticonColors {
+        scheme: info.scheme,
+        rotation: info.rotation,
+        colors: info.colors,
+    }
+}
+
+/// Everything identicon colors are calculated from: coloring scheme, its
+/// rotation, saturation and the palette, together with the resulting colors
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdenticonInfo {
+    /// Coloring scheme
+    pub scheme: Scheme,
+    /// Rotation of the coloring scheme, see [`IdenticonColors::rotation`]
+    pub rotation: u8,
+    /// HSL saturation of palette colors, in percents, from 30 to 109
+    ///
+    /// Values over 100 are used as is, taken from js code.
+    pub saturation: u8,
+    /// Palette of 64 colors, one for each byte of the input hash;
+    /// coloring scheme picks the identicon colors from it
+    #[cfg_attr(feature = "serde", serde(with = "palette_serde"))]
+    pub palette: [Color; 64],
+    /// Colors of 19 small circles, in [`crate::circles::position_circle_set`] order
+    pub colors: [Color; 19],
+}
+
+/// Serde for 64-element palette, as a sequence of colors
+#[cfg(feature = "serde")]
+mod palette_serde {
+    use super::Color;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        palette: &[Color; 64],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(palette.iter())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[Color; 64], D::Error> {
+        let palette = Vec::<Color>::deserialize(deserializer)?;
+        let len = palette.len();
+        palette
+            .try_into()
+            .map_err(|_| serde::de::Error::invalid_length(len, &"64 colors"))
+    }
+}
+
+/// Function to calculate identicon colors from `&[u8]` input slice, with
+/// all intermediate data: coloring scheme, rotation, saturation and palette.
+///
+/// Could be used to explain why two identicons look similar, for example,
+/// if they share the coloring scheme and have close palette colors.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::colors::{get_colors, get_identicon_info, Scheme};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let info = get_identicon_info(alice);
+/// assert_eq!(info.scheme, Scheme::VMirror);
+/// assert_eq!(info.saturation, 56);
+/// assert_eq!(info.colors, get_colors(alice));
+/// assert!(info.colors.iter().all(|color| info.palette.contains(color)));
+/// ```
+pub fn get_identicon_info(into_id: &[u8]) -> IdenticonInfo {
+    get_identicon_info_with_style(into_id, &Style::default())
+}
+
+/// Function to calculate identicon colors from `&[u8]` input slice, with
+/// all intermediate data, and with blank circles (palette byte `255`)
+/// colored as set in the [`Style`]
+pub fn get_identicon_info_with_style(into_id: &[u8], style: &Style) -> IdenticonInfo {
     let into_zero = &[0u8; 32];
-    let zero = blake2b(64, &[], into_zero).as_bytes().to_vec();
+    let zero = blake2b(64, &[], into_zero);
 
-    let id_prep = blake2b(64, &[], into_id).as_bytes().to_vec();
+    let id_prep = blake2b(64, &[], into_id);
 
-    let mut id: Vec<u8> = Vec::with_capacity(64);
-    for (i, x) in id_prep.iter().enumerate() {
-        let new = x.wrapping_sub(zero[i]);
-        id.push(new);
+    let mut id = [0u8; 64];
+    for (i, x) in id_prep.as_bytes().iter().enumerate() {
+        id[i] = x.wrapping_sub(zero.as_bytes()[i]);
     }
 
     // Since `id[29]` is u8, `sat` could range from 30 to 109, i.e. it always fits into u8.
     // Transformation of id[29] into u16 is to avoid overflow in multiplication
     // (wrapping could be used, but is more bulky).
-    // TODO For color calculation `sat` is used as saturation in percents
+    // For color calculation `sat` is used as saturation in percents
     // (this is taken as is from js code).
-    // However, this way `sat_component` could have values above 1.00.
-    // Palette crate does not check at this moment that `sat_component` is not
-    // overflowing 1.00, and produces some kind of resulting color.
-    // Need to find out what should have happened if the sat values are above 100.
+    // However, this way saturation component could have values above 1.00.
+    // Browsers clamp it to 1.00, palette crate does not; see `ColorMode`.
     let sat = (((id[29] as u16 * 70 / 256 + 26) % 80) + 30) as u8;
-    let sat_component: f64 = (sat as f64) / 100f64;
 
-    // calculating palette: set of 32 RGBA colors to be used in drawing
+    // calculating palette: set of 64 RGBA colors to be used in drawing
     // only id vector is used for this calculation
-    let mut my_palette: Vec<Color> = Vec::with_capacity(64);
+    let mut my_palette = [Color::background(); 64];
     for (i, x) in id.iter().enumerate() {
         let b = x.wrapping_add((i as u8 % 28).wrapping_mul(58));
         let new = match b {
-            0 => Color {
-                red: 4,
-                green: 4,
-                blue: 4,
-                alpha: 255,
+            0 => match style.color_mode {
+                ColorMode::Palette => Color {
+                    red: 4,
+                    green: 4,
+                    blue: 4,
+                    alpha: 255,
+                },
+                // `#444` in js code
+                ColorMode::Js => Color {
+                    red: 68,
+                    green: 68,
+                    blue: 68,
+                    alpha: 255,
+                },
             },
-            255 => Color::foreground(), // small circle is transparent, thus whatever is underneath it goes into `png` data, underneath is the foreground-colored large circle
-            _ => Color::derive(b, sat_component),
+            255 => style.blank, // small circle is transparent in js code, thus whatever is underneath it goes into `png` data, by default underneath is the foreground-colored large circle
+            _ => Color::derive(b, sat, style.color_mode),
         };
-        my_palette.push(new);
+        my_palette[i] = new;
     }
 
     // loading default coloring schemes
@@ -200,23 +465,26 @@ pub fn get_colors(into_id: &[u8]) -> [Color; 19] {
     let rot = (id[28] % 6) * 3;
 
     // picking colors from palette using coloring scheme with rotation applied
-    let mut my_colors: Vec<Color> = Vec::with_capacity(19);
-    for i in 0..19 {
+    let mut my_colors = [Color::background(); 19];
+    for (i, color) in my_colors.iter_mut().enumerate() {
         let num_color = {
             if i < 18 {
-                (i + rot) % 18
+                (i + rot as usize) % 18
             } else {
                 18
             }
-        } as usize;
+        };
         let num_palette = my_scheme.colors[num_color];
-        let color = my_palette[num_palette];
-        my_colors.push(color);
+        *color = my_palette[num_palette];
     }
 
-    my_colors
-        .try_into()
-        .expect("always generate 19-element set")
+    IdenticonInfo {
+        scheme: my_scheme.scheme,
+        rotation: rot,
+        saturation: sat,
+        palette: my_palette,
+        colors: my_colors,
+    }
 }
 
 /// Function to choose the coloring scheme based on value d.
@@ -325,8 +593,8 @@ mod tests {
     #[test]
     fn colors_derive() {
         let b: u8 = 212u8;
-        let saturation = 0.56;
-        let color = Color::derive(b, saturation);
+        let saturation = 56;
+        let color = Color::derive(b, saturation, ColorMode::Palette);
         assert_eq!(
             color,
             Color {
@@ -336,5 +604,153 @@ mod tests {
                 alpha: 255,
             }
         );
+        assert_eq!(Color::derive(b, saturation, ColorMode::Js), color);
+    }
+
+    #[test]
+    fn color_modes_saturation() {
+        // saturation over 100% is clamped in js mode only
+        let palette = Color::derive(1, 109, ColorMode::Palette);
+        let js = Color::derive(1, 109, ColorMode::Js);
+        assert_ne!(palette, js);
+        assert_eq!(js, Color::derive(1, 100, ColorMode::Palette));
+    }
+
+    /// Colors of polkadot.js identicons for 4096 public keys, see the file
+    /// header for the generating script
+    #[test]
+    fn conformance_vectors() {
+        let style = Style {
+            color_mode: ColorMode::Js,
+            ..Style::default()
+        };
+        let vectors = include_str!("../test_pics/polkadot_js_vectors.txt");
+        let mut failures = Vec::new();
+        let mut count = 0;
+        for line in vectors.lines().filter(|line| !line.starts_with('#')) {
+            let fields: Vec<&str> = line.split(' ').collect();
+            assert_eq!(fields.len(), 22, "malformed line {}", line);
+            let key: Vec<u8> = (0..fields[0].len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&fields[0][i..i + 2], 16).unwrap())
+                .collect();
+            let info = get_identicon_info_with_style(&key, &style);
+            if info.scheme.name() != fields[1] {
+                failures.push(format!("{}: scheme {}", fields[0], info.scheme.name()));
+            }
+            if info.rotation.to_string() != fields[2] {
+                failures.push(format!("{}: rotation {}", fields[0], info.rotation));
+            }
+            for (i, (color, expected)) in info.colors.iter().zip(&fields[3..]).enumerate() {
+                let expected = match *expected {
+                    "transparent" => style.blank,
+                    rgb => {
+                        let [_, red, green, blue] =
+                            u32::from_str_radix(rgb, 16).unwrap().to_be_bytes();
+                        Color {
+                            red,
+                            green,
+                            blue,
+                            alpha: 255,
+                        }
+                    }
+                };
+                if *color != expected {
+                    // circle numbering as in `coloring_order`
+                    failures.push(format!(
+                        "{}: circle {}, got {:?}, expected {:?}",
+                        fields[0], i, color, expected
+                    ));
+                }
+            }
+            count += 1;
+        }
+        assert_eq!(count, 4096);
+        assert!(
+            failures.is_empty(),
+            "{} discrepancies:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+
+    #[test]
+    fn info_bob() {
+        let info = get_identicon_info(BOB);
+        assert_eq!(info.colors, bob_website());
+        assert!((30..110).contains(&info.saturation));
+        assert_eq!(info.rotation % 3, 0);
+        // colors are picked from the palette by the rotated scheme
+        let scheme = default_schemes()
+            .into_iter()
+            .find(|a| a.scheme == info.scheme)
+            .unwrap();
+        for (i, color) in info.colors.iter().enumerate() {
+            let position = if i < 18 {
+                (i + info.rotation as usize) % 18
+            } else {
+                18
+            };
+            assert_eq!(*color, info.palette[scheme.colors[position]]);
+        }
+    }
+
+    /// Tests of `serde` feature, the only users of `bincode` and `serde_json`
+    /// dev-dependencies
+    #[cfg(feature = "serde")]
+    mod serde {
+        use super::*;
+
+        #[test]
+        fn color_serde_formats() {
+            let color = alice_website()[0];
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(json, "\"#a5e39cff\"");
+            assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+            let bytes = bincode::serialize(&color).unwrap();
+            assert_eq!(&bytes[bytes.len() - 4..], &[165, 227, 156, 255]);
+            assert_eq!(bincode::deserialize::<Color>(&bytes).unwrap(), color);
+            for invalid in [
+                "\"a5e39cff\"",
+                "\"#a5e39c\"",
+                "\"#a5e39cfg\"",
+                "\"#+5e39cff\"",
+            ] {
+                assert!(
+                    serde_json::from_str::<Color>(invalid).is_err(),
+                    "{}",
+                    invalid
+                );
+            }
+        }
+
+        #[test]
+        fn identicon_colors_serde() {
+            let identicon_colors = get_identicon_colors(BOB);
+            let json = serde_json::to_value(identicon_colors).unwrap();
+            assert_eq!(json["scheme"], identicon_colors.scheme.name());
+            assert_eq!(json["rotation"], identicon_colors.rotation);
+            assert_eq!(json["colors"][0], "#3a783dff");
+            assert_eq!(
+                serde_json::from_value::<IdenticonColors>(json).unwrap(),
+                identicon_colors
+            );
+            let bytes = bincode::serialize(&identicon_colors).unwrap();
+            assert_eq!(
+                bincode::deserialize::<IdenticonColors>(&bytes).unwrap(),
+                identicon_colors
+            );
+        }
+
+        #[test]
+        fn info_serde() {
+            let info = get_identicon_info(ALICE);
+            let json = serde_json::to_value(info).unwrap();
+            assert_eq!(json["scheme"], "vmirror");
+            assert_eq!(json["palette"].as_array().unwrap().len(), 64);
+            assert_eq!(serde_json::from_value::<IdenticonInfo>(json).unwrap(), info);
+            let bytes = bincode::serialize(&info).unwrap();
+            assert_eq!(bincode::deserialize::<IdenticonInfo>(&bytes).unwrap(), info);
+        }
     }
 }
@@ -0,0 +1,291 @@
+//! Textual description of the identicon, for `alt` text and screen readers
+//!
+//! Description is made of the coloring scheme and the circle colors. Each
+//! color is named after the perceptually nearest color (by CIEDE2000
+//! difference, see [`crate::similarity::color_difference`]) in the built-in
+//! table of named colors, so the description is stable for given input.
+//!
+//! Short form names the pattern, two most frequent colors of the rings and
+//! the center color, e.g. `"flower pattern, teal and dark slate blue,
+//! center crimson"`. Long form describes outer ring, inner ring and center
+//! separately.
+use crate::circles::circle_ring;
+use crate::colors::{get_identicon_colors, Color, IdenticonColors, Scheme};
+use crate::similarity::color_difference;
+
+/// Named colors: CSS color names, and very dark shades
+///
+/// A quarter of identicon palette colors have lightness 15%, without the
+/// dark shades all of them would be named `black`.
+#[rustfmt::skip]
+const NAMED_COLORS: [(&str, [u8; 3]); 90] = [
+    ("black", [0, 0, 0]),
+    ("dim gray", [105, 105, 105]),
+    ("gray", [128, 128, 128]),
+    ("dark gray", [169, 169, 169]),
+    ("silver", [192, 192, 192]),
+    ("light gray", [211, 211, 211]),
+    ("white", [255, 255, 255]),
+    ("maroon", [128, 0, 0]),
+    ("dark red", [139, 0, 0]),
+    ("brown", [165, 42, 42]),
+    ("firebrick", [178, 34, 34]),
+    ("crimson", [220, 20, 60]),
+    ("red", [255, 0, 0]),
+    ("indian red", [205, 92, 92]),
+    ("salmon", [250, 128, 114]),
+    ("coral", [255, 127, 80]),
+    ("tomato", [255, 99, 71]),
+    ("orange red", [255, 69, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("sienna", [160, 82, 45]),
+    ("saddle brown", [139, 69, 19]),
+    ("peru", [205, 133, 63]),
+    ("tan", [210, 180, 140]),
+    ("dark orange", [255, 140, 0]),
+    ("orange", [255, 165, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gold", [255, 215, 0]),
+    ("khaki", [240, 230, 140]),
+    ("yellow", [255, 255, 0]),
+    ("olive", [128, 128, 0]),
+    ("dark olive green", [85, 107, 47]),
+    ("olive drab", [107, 142, 35]),
+    ("yellow green", [154, 205, 50]),
+    ("lime green", [50, 205, 50]),
+    ("light green", [144, 238, 144]),
+    ("green", [0, 128, 0]),
+    ("dark green", [0, 100, 0]),
+    ("forest green", [34, 139, 34]),
+    ("sea green", [46, 139, 87]),
+    ("medium sea green", [60, 179, 113]),
+    ("spring green", [0, 255, 127]),
+    ("aquamarine", [127, 255, 212]),
+    ("teal", [0, 128, 128]),
+    ("dark slate gray", [47, 79, 79]),
+    ("dark cyan", [0, 139, 139]),
+    ("light sea green", [32, 178, 170]),
+    ("turquoise", [64, 224, 208]),
+    ("cyan", [0, 255, 255]),
+    ("pale turquoise", [175, 238, 238]),
+    ("cadet blue", [95, 158, 160]),
+    ("steel blue", [70, 130, 180]),
+    ("sky blue", [135, 206, 235]),
+    ("light blue", [173, 216, 230]),
+    ("dodger blue", [30, 144, 255]),
+    ("royal blue", [65, 105, 225]),
+    ("blue", [0, 0, 255]),
+    ("medium blue", [0, 0, 205]),
+    ("navy", [0, 0, 128]),
+    ("midnight blue", [25, 25, 112]),
+    ("slate blue", [106, 90, 205]),
+    ("dark slate blue", [72, 61, 139]),
+    ("indigo", [75, 0, 130]),
+    ("blue violet", [138, 43, 226]),
+    ("dark violet", [148, 0, 211]),
+    ("purple", [128, 0, 128]),
+    ("dark magenta", [139, 0, 139]),
+    ("medium purple", [147, 112, 219]),
+    ("orchid", [218, 112, 214]),
+    ("violet", [238, 130, 238]),
+    ("plum", [221, 160, 221]),
+    ("thistle", [216, 191, 216]),
+    ("lavender", [230, 230, 250]),
+    ("magenta", [255, 0, 255]),
+    ("medium violet red", [199, 21, 133]),
+    ("deep pink", [255, 20, 147]),
+    ("hot pink", [255, 105, 180]),
+    ("pink", [255, 192, 203]),
+    ("pale violet red", [219, 112, 147]),
+    ("rosy brown", [188, 143, 143]),
+    ("beige", [245, 245, 220]),
+    ("wheat", [245, 222, 179]),
+    ("dark khaki", [189, 183, 107]),
+    ("very dark red", [61, 15, 15]),
+    ("dark brown", [61, 38, 15]),
+    ("very dark olive", [56, 61, 15]),
+    ("very dark green", [15, 61, 15]),
+    ("very dark teal", [15, 61, 53]),
+    ("very dark blue", [15, 23, 61]),
+    ("very dark purple", [38, 15, 61]),
+    ("very dark magenta", [61, 15, 50]),
+];
+
+/// Description form
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Form {
+    /// Single line, for `alt` text
+    #[default]
+    Short,
+    /// Full description of the rings and the center
+    Long,
+}
+
+/// Name of the perceptually nearest named color, alpha is ignored
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{description::color_name, Color};
+///
+/// assert_eq!(color_name(&Color{red: 230, green: 30, blue: 60, alpha: 255}), "crimson");
+/// assert_eq!(color_name(&Color::foreground()), "white");
+/// ```
+pub fn color_name(color: &Color) -> &'static str {
+    NAMED_COLORS
+        .iter()
+        .map(|(name, [red, green, blue])| {
+            let named = Color {
+                red: *red,
+                green: *green,
+                blue: *blue,
+                alpha: 255,
+            };
+            (name, color_difference(color, &named))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(name, _)| *name)
+        .expect("table is not empty")
+}
+
+/// Pattern name of the coloring scheme
+pub fn pattern_name(scheme: Scheme) -> &'static str {
+    match scheme {
+        Scheme::Target => "target",
+        Scheme::Cube => "cube",
+        Scheme::Quazar => "quasar",
+        Scheme::Flower => "flower",
+        Scheme::Cyclic => "cyclic",
+        Scheme::VMirror => "vertical mirror",
+        Scheme::HMirror => "horizontal mirror",
+    }
+}
+
+/// Color names of the circles with given indices, most frequent first,
+/// same frequency in order of the first appearance
+fn names_by_frequency<I: IntoIterator<Item = usize>>(
+    colors: &[Color; 19],
+    indices: I,
+) -> Vec<&'static str> {
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    for i in indices {
+        let name = color_name(&colors[i]);
+        match counts.iter_mut().find(|(a, _)| *a == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+    // stable sort keeps the order of the first appearance
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts.into_iter().map(|(name, _)| name).collect()
+}
+
+/// Names joined in English list: `a`, `a and b`, `a, b and c`
+fn join_names(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [single] => single.to_string(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// Description of the identicon with given colors and scheme
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{colors::get_identicon_colors, description::{description_with_colors, Form}};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let short = description_with_colors(&get_identicon_colors(alice), Form::Short);
+/// assert!(short.starts_with("vertical mirror pattern, "));
+/// ```
+pub fn description_with_colors(identicon_colors: &IdenticonColors, form: Form) -> String {
+    let colors = &identicon_colors.colors;
+    let pattern = pattern_name(identicon_colors.scheme);
+    let center = color_name(&colors[18]);
+    match form {
+        Form::Short => {
+            let names = names_by_frequency(colors, (0..19).filter(|i| circle_ring(*i) != 0));
+            let shown = &names[..names.len().min(2)];
+            format!(
+                "{} pattern, {}, center {}",
+                pattern,
+                join_names(shown),
+                center
+            )
+        }
+        Form::Long => {
+            let ring = |number: u8| {
+                join_names(&names_by_frequency(
+                    colors,
+                    (0..19).filter(|i| circle_ring(*i) == number),
+                ))
+            };
+            format!(
+                "Identicon of 19 circles with {} pattern. Outer ring: {}. Inner ring: {}. Center: {}.",
+                pattern,
+                ring(2),
+                ring(1),
+                center
+            )
+        }
+    }
+}
+
+/// Description of the identicon for `&[u8]` input slice
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::description::{description, Form};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let long = description(alice, Form::Long);
+/// assert!(long.starts_with("Identicon of 19 circles with vertical mirror pattern. Outer ring: "));
+/// ```
+pub fn description(into_id: &[u8], form: Form) -> String {
+    description_with_colors(&get_identicon_colors(into_id), form)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &[u8] = &[
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    #[test]
+    fn named_colors_are_named_after_themselves() {
+        for (name, [red, green, blue]) in NAMED_COLORS.iter() {
+            let color = Color {
+                red: *red,
+                green: *green,
+                blue: *blue,
+                alpha: 255,
+            };
+            assert_eq!(color_name(&color), *name);
+        }
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(join_names(&["teal"]), "teal");
+        assert_eq!(join_names(&["teal", "gold"]), "teal and gold");
+        assert_eq!(join_names(&["teal", "gold", "red"]), "teal, gold and red");
+    }
+
+    #[test]
+    fn alice_descriptions() {
+        assert_eq!(
+            description(ALICE, Form::Short),
+            "vertical mirror pattern, blue violet and yellow green, center dark slate blue"
+        );
+        assert_eq!(
+            description(ALICE, Form::Long),
+            "Identicon of 19 circles with vertical mirror pattern. Outer ring: light green, blue violet, medium violet red, yellow green, dark cyan, plum and very dark green. Inner ring: dark brown, blue violet and yellow green. Center: dark slate blue."
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Errors in identicon generation
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "ss58")]
+use crate::input::InputError;
+
+/// Errors in identicon generation, common for all crate features
+#[derive(Debug)]
+pub enum IdenticonError {
+    /// Requested image size is not acceptable, for example, zero
+    InvalidSize(u32),
+    /// Requested scaling factor is not acceptable, for example, zero
+    InvalidScalingFactor(u32),
+    /// Image size multiplied by scaling factor exceeds `i32::MAX`, or the
+    /// resulting RGBA8 pixel data length exceeds `isize::MAX` bytes
+    SizeOverflow {
+        size_in_pixels: u32,
+        scaling_factor: u32,
+    },
+    /// [`png::EncodingError`](https://docs.rs/png/latest/png/enum.EncodingError.html)
+    ///
+    /// From `png` crate, could appear on writing the pixel data into `png`,
+    /// generally should not happen, since the `png` parameters are matching the pixel data generated
+    #[cfg(feature = "pix")]
+    Png(png::EncodingError),
+    /// Caller-supplied buffer is too small to fit the pixel data;
+    /// `required` is `usize::MAX` if the required length overflows `usize`
+    BufferTooSmall { length: usize, required: usize },
+    /// Caller-supplied buffer stride is smaller than the image row length
+    InvalidStride { stride: usize, minimum: usize },
+    /// Badge logo could not be drawn in requested image format, for
+    /// example, `svg` logo in `png` image
+    UnsupportedLogo,
+    /// Badge logo is not valid: RGBA logo data length does not match its
+    /// size, or `svg` logo is not a single `svg` element
+    InvalidLogo,
+    /// Identicon theme name is not known
+    #[cfg(feature = "alloc")]
+    UnknownTheme(String),
+    /// [`InputError`]
+    ///
+    /// Identicon input string could not be decoded
+    #[cfg(feature = "ss58")]
+    Input(InputError),
+}
+
+impl core::fmt::Display for IdenticonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self {
+            IdenticonError::InvalidSize(a) => write!(f, "Invalid image size {}.", a),
+            IdenticonError::InvalidScalingFactor(a) => write!(f, "Invalid scaling factor {}.", a),
+            IdenticonError::SizeOverflow {
+                size_in_pixels,
+                scaling_factor,
+            } => write!(
+                f,
+                "Image size {} with scaling factor {} is too large.",
+                size_in_pixels, scaling_factor
+            ),
+            #[cfg(feature = "pix")]
+            IdenticonError::Png(e) => write!(f, "Error encoding data into png format: {}", e),
+            IdenticonError::BufferTooSmall { length, required } => write!(
+                f,
+                "Buffer length {} is too small, pixel data requires {} bytes.",
+                length, required
+            ),
+            IdenticonError::InvalidStride { stride, minimum } => write!(
+                f,
+                "Buffer stride {} is smaller than image row length {}.",
+                stride, minimum
+            ),
+            IdenticonError::UnsupportedLogo => {
+                write!(f, "Badge logo could not be drawn in this image format.")
+            }
+            IdenticonError::InvalidLogo => write!(f, "Invalid badge logo."),
+            #[cfg(feature = "alloc")]
+            IdenticonError::UnknownTheme(a) => write!(f, "Unknown identicon theme {}.", a),
+            #[cfg(feature = "ss58")]
+            IdenticonError::Input(e) => write!(f, "Error decoding identicon input: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IdenticonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            #[cfg(feature = "pix")]
+            IdenticonError::Png(e) => Some(e),
+            #[cfg(feature = "ss58")]
+            IdenticonError::Input(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "pix")]
+impl From<png::EncodingError> for IdenticonError {
+    fn from(e: png::EncodingError) -> Self {
+        IdenticonError::Png(e)
+    }
+}
+
+#[cfg(feature = "ss58")]
+impl From<InputError> for IdenticonError {
+    fn from(e: InputError) -> Self {
+        IdenticonError::Input(e)
+    }
+}
+
+#[cfg(all(test, feature = "ss58"))]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn input_error_is_source() {
+        let error = IdenticonError::from(InputError::BadChecksum);
+        let source = error.source().expect("input error is chained");
+        assert_eq!(source.to_string(), InputError::BadChecksum.to_string());
+        assert!(IdenticonError::InvalidSize(0).source().is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! Golden-file regression tests of `png` and `svg` output
+//!
+//! A fixed matrix of keys, sizes, filters and formats is rendered and
+//! compared with the files in `test_pics/golden`. `svg` files are compared
+//! byte-for-byte. `png` files are decoded and compared pixel-by-pixel,
+//! with channel difference up to [`PNG_TOLERANCE`], so that the tests do
+//! not depend on `png` encoder settings.
+//!
+//! After intended rendering changes the golden files are regenerated with
+//!
+//! ```sh
+//! UPDATE_GOLDEN=1 cargo test golden
+//! ```
+//!
+//! and the changed files are reviewed and committed.
+use image::imageops::FilterType;
+use std::{fs, path::PathBuf};
+
+use crate::{
+    generate_png, generate_png_antialiased, generate_png_scaled_custom, generate_svg,
+    make_png_from_data,
+};
+
+/// Maximal difference of any RGBA channel of any pixel in `png` images
+const PNG_TOLERANCE: u8 = 1;
+
+const KEYS: [(&str, [u8; 32]); 3] = [
+    (
+        "alice",
+        [
+            212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133,
+            88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+        ],
+    ),
+    (
+        "bob",
+        [
+            142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97, 54,
+            147, 201, 18, 144, 156, 178, 38, 170, 71, 148, 242, 106, 72,
+        ],
+    ),
+    ("zero", [0; 32]),
+];
+
+const SIZES: [u16; 3] = [16, 30, 64];
+
+const FILTERS: [(&str, FilterType); 5] = [
+    ("nearest", FilterType::Nearest),
+    ("triangle", FilterType::Triangle),
+    ("catmullrom", FilterType::CatmullRom),
+    ("gaussian", FilterType::Gaussian),
+    ("lanczos3", FilterType::Lanczos3),
+];
+
+/// Scaling factor of the scaled `png` images, same as in Signer
+const SCALING_FACTOR: u32 = 5;
+
+/// Golden file name and generated content
+fn matrix() -> Vec<(String, Vec<u8>)> {
+    let mut files = Vec::new();
+    for (name, key) in KEYS.iter() {
+        files.push((
+            format!("{}.svg", name),
+            generate_svg(key).to_string().into_bytes(),
+        ));
+        for size in SIZES {
+            files.push((
+                format!("{}_{}.png", name, size),
+                generate_png(key, size).unwrap(),
+            ));
+            files.push((
+                format!("{}_{}_antialiased.png", name, size),
+                generate_png_antialiased(key, size).unwrap(),
+            ));
+            for (filter_name, filter) in FILTERS {
+                files.push((
+                    format!("{}_{}_{}.png", name, size, filter_name),
+                    generate_png_scaled_custom(key, size as u32, SCALING_FACTOR, filter).unwrap(),
+                ));
+            }
+        }
+    }
+    files
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_pics/golden")
+}
+
+/// Difference between the generated and the golden file, if any
+fn compare(file_name: &str, generated: &[u8], golden: &[u8]) -> Option<String> {
+    if !file_name.ends_with(".png") {
+        return (generated != golden).then(|| "content differs".to_string());
+    }
+    let generated = image::load_from_memory(generated).unwrap().into_rgba8();
+    let golden = match image::load_from_memory(golden) {
+        Ok(a) => a.into_rgba8(),
+        Err(e) => return Some(format!("golden file could not be decoded: {}", e)),
+    };
+    if generated.dimensions() != golden.dimensions() {
+        return Some(format!(
+            "size {:?}, expected {:?}",
+            generated.dimensions(),
+            golden.dimensions()
+        ));
+    }
+    let mut pixels = 0;
+    let mut max_difference = 0;
+    for (a, b) in generated.pixels().zip(golden.pixels()) {
+        let difference = (0..4).map(|i| a[i].abs_diff(b[i])).max().unwrap();
+        if difference > PNG_TOLERANCE {
+            pixels += 1;
+        }
+        max_difference = max_difference.max(difference);
+    }
+    (pixels != 0).then(|| {
+        format!(
+            "{} pixels differ, channel difference up to {}",
+            pixels, max_difference
+        )
+    })
+}
+
+#[test]
+fn golden_files() {
+    let dir = golden_dir();
+    let files = matrix();
+    if std::env::var("UPDATE_GOLDEN").as_deref() == Ok("1") {
+        fs::create_dir_all(&dir).unwrap();
+        for entry in fs::read_dir(&dir).unwrap() {
+            fs::remove_file(entry.unwrap().path()).unwrap();
+        }
+        for (file_name, content) in files.iter() {
+            fs::write(dir.join(file_name), content).unwrap();
+        }
+        return;
+    }
+    let mut failures = Vec::new();
+    for (file_name, content) in files.iter() {
+        match fs::read(dir.join(file_name)) {
+            Ok(golden) => {
+                if let Some(difference) = compare(file_name, content, &golden) {
+                    failures.push(format!("{}: {}", file_name, difference));
+                }
+            }
+            Err(_) => failures.push(format!("{}: golden file is missing", file_name)),
+        }
+    }
+    for entry in fs::read_dir(&dir).unwrap() {
+        let file_name = entry.unwrap().file_name().into_string().unwrap();
+        if !files.iter().any(|(name, _)| *name == file_name) {
+            failures.push(format!("{}: not in the test matrix", file_name));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "Rendering differs from golden files, if intended, regenerate them with UPDATE_GOLDEN=1:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn tolerance() {
+    let png = generate_png(&KEYS[0].1, 16).unwrap();
+    assert_eq!(compare("alice.png", &png, &png), None);
+    let mut image = image::load_from_memory(&png).unwrap().into_rgba8();
+    let pixel = image.get_pixel_mut(8, 8);
+    let red = pixel.0[0];
+    pixel.0[0] = if red < 128 {
+        red + PNG_TOLERANCE
+    } else {
+        red - PNG_TOLERANCE
+    };
+    let within = make_png_from_data(&image, 16).unwrap();
+    assert_eq!(compare("alice.png", &within, &png), None);
+    let pixel = image.get_pixel_mut(8, 8);
+    pixel.0[0] = if red < 128 {
+        red + PNG_TOLERANCE + 1
+    } else {
+        red - PNG_TOLERANCE - 1
+    };
+    let beyond = make_png_from_data(&image, 16).unwrap();
+    assert!(compare("alice.png", &beyond, &png).is_some());
+    assert!(compare("alice.svg", b"<svg/>", b"<svg />").is_some());
+}
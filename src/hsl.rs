@@ -0,0 +1,316 @@
+//! HSL to RGB conversion of the palette colors
+//!
+//! Palette colors are set by integer hue in degrees, and integer saturation
+//! and lightness in percents, and are converted into RGB either as in
+//! `palette` crate, or as in browser `hsl()` CSS function (see
+//! [`ColorMode`]), both with `f64` components. Fixed-point conversion
+//! reproduces both exactly, with integer arithmetic only, and is used with
+//! feature `"fixed_point"`, or if `palette` is not available.
+//!
+//! All components are calculated exactly, as fractions with denominator
+//! `SCALE`. Component values that are exact halves after scaling into
+//! `0..=255` range are rounded by `f64` rounding errors, these are listed
+//! in [`ROUNDED_UP`] for `palette`, and in [`ROUNDED_DOWN_JS`] for CSS.
+//!
+//! The tables follow no rounding rule on the exact fraction: the direction
+//! is set by the order of `f64` operations in each conversion. They were
+//! found by converting every palette color with [`to_rgb_float`] and listing
+//! the exact halves it rounds against the default direction; test
+//! `tie_tables` repeats this and checks the tables are complete.
+use crate::colors::ColorMode;
+
+/// Common denominator of the exact RGB components: percents of lightness,
+/// percents of saturation, and 60 degrees of hue sector
+#[cfg(any(test, feature = "fixed_point", not(feature = "palette")))]
+const SCALE: i64 = 100 * 100 * 60;
+
+/// Part of the RGB color, as in `palette` conversion
+#[cfg(any(test, feature = "fixed_point", not(feature = "palette")))]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Part {
+    /// Largest component, chroma over the minimum
+    Chroma,
+    /// Second component, set by the hue remainder modulo 120 degrees
+    Second(u16),
+    /// Smallest component, the minimum only
+    Zero,
+}
+
+/// Exact halves rounded up by `palette`: lightness and saturation in
+/// percents, and the color part; other exact halves are rounded down
+#[cfg(any(test, feature = "fixed_point", not(feature = "palette")))]
+#[rustfmt::skip]
+const ROUNDED_UP: [(u8, u8, Part); 18] = [
+    (15, 100, Part::Chroma),
+    (15, 100, Part::Second(20)),
+    (15, 100, Part::Second(60)),
+    (35, 40, Part::Second(5)),
+    (35, 100, Part::Second(20)),
+    (75, 30, Part::Second(110)),
+    (75, 40, Part::Second(15)),
+    (75, 40, Part::Second(105)),
+    (75, 50, Part::Second(118)),
+    (75, 56, Part::Second(5)),
+    (75, 60, Part::Second(20)),
+    (75, 72, Part::Second(65)),
+    (75, 90, Part::Second(50)),
+    (75, 100, Part::Zero),
+    (75, 100, Part::Second(0)),
+    (75, 100, Part::Second(16)),
+    (75, 100, Part::Second(24)),
+    (75, 100, Part::Second(80)),
+];
+
+/// Exact halves rounded down by CSS conversion, with saturation clamped to
+/// 100: lightness and saturation in percents, and the color part; other
+/// exact halves are rounded up, as in `Math.round`
+#[cfg(any(test, feature = "fixed_point", not(feature = "palette")))]
+#[rustfmt::skip]
+const ROUNDED_DOWN_JS: [(u8, u8, Part); 16] = [
+    (15, 40, Part::Second(5)),
+    (15, 50, Part::Second(110)),
+    (35, 40, Part::Second(5)),
+    (35, 50, Part::Second(110)),
+    (75, 40, Part::Second(35)),
+    (75, 40, Part::Second(65)),
+    (75, 50, Part::Second(50)),
+    (75, 50, Part::Second(86)),
+    (75, 56, Part::Second(5)),
+    (75, 70, Part::Second(110)),
+    (75, 72, Part::Second(65)),
+    (75, 75, Part::Second(22)),
+    (75, 88, Part::Second(5)),
+    (75, 90, Part::Second(50)),
+    (75, 100, Part::Second(48)),
+    (75, 100, Part::Second(88)),
+];
+
+/// Convert HSL color into `u8` RGB components, with integer arithmetic only
+///
+/// Hue is in degrees, below 360, saturation and lightness are in percents.
+/// Saturation over 100 is clamped in [`ColorMode::Js`], and is processed
+/// same as in `palette` in [`ColorMode::Palette`], with resulting components
+/// clamped.
+#[cfg(any(test, feature = "fixed_point", not(feature = "palette")))]
+pub(crate) fn to_rgb_fixed(hue: u16, saturation: u8, lightness: u8, mode: ColorMode) -> [u8; 3] {
+    let saturation = match mode {
+        ColorMode::Palette => saturation,
+        ColorMode::Js => saturation.min(100),
+    };
+    exact(hue, saturation, lightness).map(|(part, quotient, rest)| {
+        let round_up = match rest {
+            core::cmp::Ordering::Less => false,
+            core::cmp::Ordering::Equal => match mode {
+                ColorMode::Palette => ROUNDED_UP.contains(&(lightness, saturation, part)),
+                ColorMode::Js => !ROUNDED_DOWN_JS.contains(&(lightness, saturation, part)),
+            },
+            core::cmp::Ordering::Greater => true,
+        };
+        quotient + round_up as u8
+    })
+}
+
+/// Exact RGB components scaled into `0..=255` range: the color part, the
+/// integer part, and the remaining fraction compared with one half
+///
+/// Saturation is processed as is, clamping is done by the caller.
+#[cfg(any(test, feature = "fixed_point", not(feature = "palette")))]
+fn exact(hue: u16, saturation: u8, lightness: u8) -> [(Part, u8, core::cmp::Ordering); 3] {
+    let spread = (100 - (2 * lightness as i64 - 100).abs()) * saturation as i64;
+    let chroma = spread * 60;
+    let remainder = hue % 120;
+    let second = spread * (60 - (remainder as i64 - 60).abs());
+    // `chroma` is always even
+    let minimum = lightness as i64 * (SCALE / 100) - chroma / 2;
+    let component = |part: Part| {
+        let value = match part {
+            Part::Chroma => chroma,
+            Part::Second(_) => second,
+            Part::Zero => 0,
+        } + minimum;
+        let scaled = value.clamp(0, SCALE) * 255;
+        (
+            part,
+            (scaled / SCALE) as u8,
+            (2 * (scaled % SCALE)).cmp(&SCALE),
+        )
+    };
+    let (chroma, second, zero) = (
+        component(Part::Chroma),
+        component(Part::Second(remainder)),
+        component(Part::Zero),
+    );
+    match hue / 60 {
+        0 => [chroma, second, zero],
+        1 => [second, chroma, zero],
+        2 => [zero, chroma, second],
+        3 => [zero, second, chroma],
+        4 => [second, zero, chroma],
+        _ => [chroma, zero, second],
+    }
+}
+
+/// Convert HSL color into `u8` RGB components, with `f64` arithmetic
+#[cfg(any(test, all(feature = "palette", not(feature = "fixed_point"))))]
+pub(crate) fn to_rgb_float(hue: u16, saturation: u8, lightness: u8, mode: ColorMode) -> [u8; 3] {
+    match mode {
+        ColorMode::Palette => to_rgb_palette(hue, saturation, lightness),
+        ColorMode::Js => to_rgb_js(hue, saturation, lightness),
+    }
+}
+
+/// Convert HSL color into `u8` RGB components, with `palette`
+#[cfg(any(test, all(feature = "palette", not(feature = "fixed_point"))))]
+fn to_rgb_palette(hue: u16, saturation: u8, lightness: u8) -> [u8; 3] {
+    use palette::{FromColor, FromComponent, Hsl, RgbHue, Srgb};
+
+    // recalculated into `RgbHue`, to be used as HSL hue component
+    let h_component = RgbHue::from_degrees(hue as f64);
+
+    // recalculated in HSL components (component range is 0.00 to 1.00,
+    // saturation could be above 1.00)
+    let s_component: f64 = (saturation as f64) / 100f64;
+    let l_component: f64 = (lightness as f64) / 100f64;
+
+    // defining HSL color
+    let color_hsl = Hsl::new(h_component, s_component, l_component);
+
+    // transforming HSL color into RGB color
+    let color_srgb = Srgb::from_color(color_hsl);
+
+    // getting red, green, blue components, transforming them in 0..255 range of u8
+    [
+        u8::from_component(color_srgb.red),
+        u8::from_component(color_srgb.green),
+        u8::from_component(color_srgb.blue),
+    ]
+}
+
+/// Convert HSL color into `u8` RGB components, same as CSS Color 4
+/// reference code in javascript, with saturation clamped as in `hsl()` and
+/// components rounded with `Math.round`
+#[cfg(any(test, all(feature = "palette", not(feature = "fixed_point"))))]
+fn to_rgb_js(hue: u16, saturation: u8, lightness: u8) -> [u8; 3] {
+    let hue = hue as f64;
+    let saturation = saturation.min(100) as f64 / 100f64;
+    let lightness = lightness as f64 / 100f64;
+    let f = |n: f64| {
+        let k = (n + hue / 30f64) % 12f64;
+        let a = saturation * lightness.min(1f64 - lightness);
+        lightness - a * (k - 3f64).min(9f64 - k).clamp(-1f64, 1f64)
+    };
+    // `Math.round` rounds halves up, same as `round` for positive numbers
+    [f(0f64), f(8f64), f(4f64)].map(|x| crate::math::round(x * 255f64) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hue, saturation and lightness of all identicon palette colors
+    fn palette_colors() -> impl Iterator<Item = (u16, u8, u8)> {
+        (1..=254u8).flat_map(|b| {
+            let hue = (b as u16 % 64 * 360) / 64;
+            let lightness = [53, 15, 35, 75][b as usize / 64];
+            (30..=109).map(move |saturation| (hue, saturation, lightness))
+        })
+    }
+
+    #[test]
+    fn fixed_point_matches_palette() {
+        for (hue, saturation, lightness) in palette_colors() {
+            assert_eq!(
+                to_rgb_fixed(hue, saturation, lightness, ColorMode::Palette),
+                to_rgb_float(hue, saturation, lightness, ColorMode::Palette),
+                "hue {}, saturation {}, lightness {}",
+                hue,
+                saturation,
+                lightness
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_point_matches_js() {
+        for (hue, saturation, lightness) in palette_colors() {
+            assert_eq!(
+                to_rgb_fixed(hue, saturation, lightness, ColorMode::Js),
+                to_rgb_float(hue, saturation, lightness, ColorMode::Js),
+                "hue {}, saturation {}, lightness {}",
+                hue,
+                saturation,
+                lightness
+            );
+        }
+    }
+
+    #[test]
+    fn js_table() {
+        // every reachable hue, saturation and lightness, converted in node
+        let table = include_str!("../test_pics/hsl_js.txt");
+        let mut lines = 0;
+        for line in table.lines().filter(|line| !line.starts_with('#')) {
+            let mut fields = line.split(' ');
+            let lightness: u8 = fields.next().unwrap().parse().unwrap();
+            let saturation: u8 = fields.next().unwrap().parse().unwrap();
+            for (b, rgb) in fields.enumerate() {
+                let hue = (b as u16 * 360) / 64;
+                let [_, red, green, blue] = u32::from_str_radix(rgb, 16).unwrap().to_be_bytes();
+                let expected = [red, green, blue];
+                for result in [
+                    to_rgb_fixed(hue, saturation, lightness, ColorMode::Js),
+                    to_rgb_float(hue, saturation, lightness, ColorMode::Js),
+                ] {
+                    assert_eq!(
+                        result, expected,
+                        "hue {}, saturation {}, lightness {}",
+                        hue, saturation, lightness
+                    );
+                }
+            }
+            lines += 1;
+        }
+        assert_eq!(lines, 4 * 80);
+    }
+
+    #[test]
+    fn tie_tables() {
+        for (mode, table) in [
+            (ColorMode::Palette, &ROUNDED_UP[..]),
+            (ColorMode::Js, &ROUNDED_DOWN_JS[..]),
+        ] {
+            let mut found = Vec::new();
+            for (hue, saturation, lightness) in palette_colors() {
+                let float = to_rgb_float(hue, saturation, lightness, mode);
+                let saturation = match mode {
+                    ColorMode::Palette => saturation,
+                    ColorMode::Js => saturation.min(100),
+                };
+                let exact = exact(hue, saturation, lightness);
+                for ((part, quotient, rest), result) in exact.into_iter().zip(float) {
+                    let rounded_up = result > quotient;
+                    if rest.is_eq() && rounded_up == (mode == ColorMode::Palette) {
+                        found.push((lightness, saturation, part));
+                    }
+                }
+            }
+            found.sort();
+            found.dedup();
+            let mut table = table.to_vec();
+            table.sort();
+            assert_eq!(found, table, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn exact_halves() {
+        // 0.3 * 255 = 76.5, rounded up in `palette`
+        assert_eq!(to_rgb_fixed(0, 100, 15, ColorMode::Palette), [77, 0, 0]);
+        // 0.7 * 255 = 178.5, rounded down in `palette`
+        assert_eq!(to_rgb_fixed(0, 100, 35, ColorMode::Palette), [178, 0, 0]);
+        assert_eq!(to_rgb_fixed(0, 50, 50, ColorMode::Palette), [191, 64, 64]);
+        // `Math.round` rounds both up
+        assert_eq!(to_rgb_fixed(0, 100, 15, ColorMode::Js), [77, 0, 0]);
+        assert_eq!(to_rgb_fixed(0, 100, 35, ColorMode::Js), [179, 0, 0]);
+    }
+}
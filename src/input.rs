@@ -0,0 +1,297 @@
+//! Decoding of identicon input from text: SS58 addresses and hexadecimal public keys
+//!
+//! Identicon generators in this crate accept `&[u8]` slice. Public keys are,
+//! however, usually encountered as strings, either hexadecimal
+//! (`d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d`,
+//! with or without `0x`), or base58 network-specific SS58 addresses
+//! (`5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY`).
+//!
+//! SS58 address is a base58-encoded concatenation of network prefix
+//! (one or two bytes), account bytes and checksum. Checksum is taken from
+//! blake2b-512 hash of `"SS58PRE"` followed by prefix and account bytes.
+//! Accounts of 1, 2, 4 and 8 bytes have one-byte checksum,
+//! accounts of 32 and 33 bytes have two-byte checksum.
+use blake2_rfc::blake2b::blake2b;
+
+/// Context bytes prepended to data before checksum hashing
+const SS58_CONTEXT: &[u8] = b"SS58PRE";
+
+/// Decoded identicon input
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedInput {
+    /// Account bytes, to be used as identicon input
+    pub account: Vec<u8>,
+    /// SS58 network prefix, `None` for hexadecimal input
+    pub prefix: Option<u16>,
+}
+
+/// Decode identicon input from either SS58 address or hexadecimal public key
+///
+/// Input is processed as hexadecimal if it starts with `0x`, or if it
+/// consists of even number of hexadecimal digits only.
+/// All other inputs are processed as SS58 addresses.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::input::decode;
+///
+/// let from_address = decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
+/// let from_hex = decode("0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").unwrap();
+/// assert_eq!(from_address.account, from_hex.account);
+/// assert_eq!(from_address.prefix, Some(42));
+/// assert_eq!(from_hex.prefix, None);
+/// ```
+pub fn decode(input: &str) -> Result<DecodedInput, InputError> {
+    let input = input.trim();
+    if input.starts_with("0x") || is_hex(input) {
+        Ok(DecodedInput {
+            account: decode_hex(input)?,
+            prefix: None,
+        })
+    } else {
+        let (account, prefix) = decode_ss58(input)?;
+        Ok(DecodedInput {
+            account,
+            prefix: Some(prefix),
+        })
+    }
+}
+
+/// Decode hexadecimal public key, with or without `0x`
+///
+/// Leading and trailing whitespace is ignored.
+pub fn decode_hex(input: &str) -> Result<Vec<u8>, InputError> {
+    let input = input.trim();
+    let stripped = input.strip_prefix("0x").unwrap_or(input);
+    hex::decode(stripped).map_err(|_| InputError::InvalidHex)
+}
+
+/// Decode SS58 address into account bytes and network prefix
+///
+/// Checksum is verified. Both one-byte (`0..=63`) and two-byte
+/// (`64..=16383`) network prefixes are supported. Leading and trailing
+/// whitespace is ignored.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::input::decode_ss58;
+///
+/// let (account, prefix) = decode_ss58("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5").unwrap();
+/// assert_eq!(prefix, 0);
+/// assert_eq!(account.len(), 32);
+/// ```
+pub fn decode_ss58(address: &str) -> Result<(Vec<u8>, u16), InputError> {
+    let data = bs58::decode(address.trim())
+        .into_vec()
+        .map_err(|_| InputError::InvalidBase58)?;
+
+    let first = *data.first().ok_or(InputError::BadLength(0))?;
+    let (prefix, prefix_len) = match first {
+        0..=63 => (first as u16, 1),
+        64..=127 => {
+            let second = *data.get(1).ok_or(InputError::BadLength(data.len()))?;
+            // lower 6 bits of the first byte are the middle bits of the prefix,
+            // upper 2 bits of the second byte are the lowest bits of the prefix,
+            // lower 6 bits of the second byte are the highest bits of the prefix
+            let lower = ((first & 0b0011_1111) << 2) | (second >> 6);
+            let upper = second & 0b0011_1111;
+            (lower as u16 | ((upper as u16) << 8), 2)
+        }
+        _ => return Err(InputError::UnsupportedPrefix(first as u16)),
+    };
+
+    // prefixes 46 and 47 are reserved
+    if prefix == 46 || prefix == 47 {
+        return Err(InputError::UnsupportedPrefix(prefix));
+    }
+
+    let checksum_len = match data.len() - prefix_len {
+        2 | 3 | 5 | 9 => 1,
+        34 | 35 => 2,
+        _ => return Err(InputError::BadLength(data.len())),
+    };
+
+    let body_len = data.len() - checksum_len;
+    let hash = blake2b(64, &[], &[SS58_CONTEXT, &data[..body_len]].concat());
+    if hash.as_bytes()[..checksum_len] != data[body_len..] {
+        return Err(InputError::BadChecksum);
+    }
+
+    Ok((data[prefix_len..body_len].to_vec(), prefix))
+}
+
+/// Encode account bytes into SS58 address with given network prefix
+///
+/// Prefixes up to 63 take one byte, larger prefixes up to 16383 take two
+/// bytes; error [`InputError::UnsupportedPrefix`] for prefixes that could
+/// not be encoded. Polkadot.js `beachball` theme is seeded with the
+/// address, see [`crate::theme::Beachball`].
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::input::{encode_ss58, InputError};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// assert_eq!(encode_ss58(alice, 42).unwrap(), "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+/// assert_eq!(encode_ss58(alice, 16384), Err(InputError::UnsupportedPrefix(16384)));
+/// ```
+pub fn encode_ss58(account: &[u8], prefix: u16) -> Result<String, InputError> {
+    let mut data = match prefix {
+        0..=63 => vec![prefix as u8],
+        64..=16383 => {
+            let first = ((prefix & 0b0000_0000_1111_1100) as u8 >> 2) | 0b0100_0000;
+            let second =
+                ((prefix >> 8) as u8 & 0b0011_1111) | ((prefix & 0b0000_0000_0000_0011) as u8) << 6;
+            vec![first, second]
+        }
+        _ => return Err(InputError::UnsupportedPrefix(prefix)),
+    };
+    data.extend_from_slice(account);
+    let checksum_len = if account.len() > 8 { 2 } else { 1 };
+    let hash = blake2b(64, &[], &[SS58_CONTEXT, &data].concat());
+    data.extend_from_slice(&hash.as_bytes()[..checksum_len]);
+    Ok(bs58::encode(data).into_string())
+}
+
+/// Check if the input consists of even number of hexadecimal digits
+fn is_hex(input: &str) -> bool {
+    !input.is_empty()
+        && input.len().is_multiple_of(2)
+        && input.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Errors in decoding identicon input
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputError {
+    /// Input is not a valid hexadecimal string
+    InvalidHex,
+    /// Input is not a valid base58 string
+    InvalidBase58,
+    /// Decoded SS58 data has length not matching any known account format
+    BadLength(usize),
+    /// SS58 checksum does not match the data
+    BadChecksum,
+    /// SS58 prefix is reserved or could not be decoded
+    UnsupportedPrefix(u16),
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            InputError::InvalidHex => write!(f, "Input is not a valid hexadecimal string."),
+            InputError::InvalidBase58 => write!(f, "Input is not a valid base58 string."),
+            InputError::BadLength(a) => {
+                write!(f, "Decoded SS58 data has unexpected length {}.", a)
+            }
+            InputError::BadChecksum => write!(f, "SS58 checksum mismatch."),
+            InputError::UnsupportedPrefix(a) => write!(f, "SS58 prefix {} is not supported.", a),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &[u8] = &[
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    #[test]
+    fn alice_known_networks() {
+        for (address, expected_prefix) in [
+            ("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 42),
+            ("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 0),
+            ("HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F", 2),
+        ] {
+            let (account, prefix) = decode_ss58(address).unwrap();
+            assert_eq!(account, ALICE);
+            assert_eq!(prefix, expected_prefix);
+        }
+    }
+
+    #[test]
+    fn two_byte_prefixes() {
+        for prefix in [64, 255, 1284, 16383] {
+            let address = encode_ss58(ALICE, prefix).unwrap();
+            assert_eq!(decode_ss58(&address).unwrap(), (ALICE.to_vec(), prefix));
+        }
+    }
+
+    #[test]
+    fn short_accounts() {
+        for len in [1, 2, 4, 8, 33] {
+            let account = vec![7u8; len];
+            let address = encode_ss58(&account, 5).unwrap();
+            assert_eq!(decode_ss58(&address).unwrap(), (account, 5));
+        }
+    }
+
+    #[test]
+    fn bad_checksum() {
+        let mut data = bs58::decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .into_vec()
+            .unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        let address = bs58::encode(data).into_string();
+        assert_eq!(decode_ss58(&address), Err(InputError::BadChecksum));
+    }
+
+    #[test]
+    fn bad_length() {
+        let address = bs58::encode([42u8; 20]).into_string();
+        assert_eq!(decode_ss58(&address), Err(InputError::BadLength(20)));
+    }
+
+    #[test]
+    fn unsupported_prefix() {
+        let address = encode_ss58(ALICE, 46).unwrap();
+        assert_eq!(
+            decode_ss58(&address),
+            Err(InputError::UnsupportedPrefix(46))
+        );
+        let address = bs58::encode([128u8; 35]).into_string();
+        assert_eq!(
+            decode_ss58(&address),
+            Err(InputError::UnsupportedPrefix(128))
+        );
+        for prefix in [16384, u16::MAX] {
+            assert_eq!(
+                encode_ss58(ALICE, prefix),
+                Err(InputError::UnsupportedPrefix(prefix))
+            );
+        }
+    }
+
+    #[test]
+    fn hex_input() {
+        let hex_key = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+        for input in [hex_key.to_string(), format!("0x{}", hex_key)] {
+            assert_eq!(
+                decode(&input).unwrap(),
+                DecodedInput {
+                    account: ALICE.to_vec(),
+                    prefix: None
+                }
+            );
+        }
+        assert_eq!(decode("0xd4359"), Err(InputError::InvalidHex));
+    }
+
+    #[test]
+    fn whitespace_ignored() {
+        let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let padded = format!(" {}\n", address);
+        assert_eq!(decode_ss58(&padded), decode_ss58(address));
+        assert_eq!(decode(&padded).unwrap().prefix, Some(42));
+        let hex_key = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+        assert_eq!(decode_hex(&format!("\t{} ", hex_key)).unwrap(), ALICE);
+    }
+}
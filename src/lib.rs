@@ -7,27 +7,74 @@
 //!
 //! Crate also supports generation of identicon-like images with pre-set colors in RGBA format, mainly for test purposes.  
 //!
-//! Feature `"pix"` supports generation of `png` images, feature `"vec"` - generation of `svg` images. Both are made available by default.  
+//! Feature `"pix"` supports generation of `png` images, feature `"vec"` - generation of `svg` images, feature `"ss58"` - decoding of SS58 addresses and hexadecimal public keys as identicon input. All are made available by default. Feature `"serde"` enables serialization of identicon colors. Feature `"fixed_point"` makes color calculation integer-only, with the same results.  
+//!
+//! Crate is `no_std` without feature `"std"` (enabled by default, and required by all of the above). Identicon colors and the pure-Rust rasterizer into caller-supplied buffer (see `write_rgba`) are always available, feature `"alloc"` adds scenes and badges.  
 
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
 #![deny(unused_crate_dependencies)]
+// dev-dependencies `bincode` and `serde_json` are used only in tests of
+// `serde` feature
+#![cfg_attr(all(test, not(feature = "serde")), allow(unused_crate_dependencies))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// with fixed-point colors `palette` is used only in `std` modules
+#[cfg(all(feature = "palette", feature = "fixed_point", not(feature = "std")))]
+use palette as _;
 
 #[cfg(feature = "pix")]
 use image::{
     imageops::{resize, FilterType},
-    load_from_memory,
+    RgbaImage,
 };
 
 #[cfg(feature = "vec")]
 use svg::Document;
 
+#[cfg(feature = "alloc")]
+pub mod badge;
+#[cfg(feature = "std")]
+pub mod beachball;
+#[cfg(feature = "std")]
+pub mod blockies;
 pub mod circles;
 pub mod colors;
 pub use colors::Color;
+#[cfg(all(feature = "std", feature = "palette"))]
+pub mod description;
+pub mod error;
+pub use error::IdenticonError;
+#[cfg(all(test, feature = "pix", feature = "vec"))]
+mod golden;
+mod hsl;
+#[cfg(feature = "ss58")]
+pub mod input;
+#[cfg(feature = "std")]
+pub mod jdenticon;
+mod math;
+#[cfg(feature = "alloc")]
+mod raster;
+#[cfg(feature = "alloc")]
+pub mod scene;
+#[cfg(feature = "alloc")]
+pub use scene::Scene;
+#[cfg(all(feature = "std", feature = "palette"))]
+pub mod similarity;
+pub mod style;
+pub use style::Style;
+#[cfg(feature = "std")]
+pub mod theme;
+#[cfg(feature = "std")]
+pub use theme::Theme;
+#[cfg(all(feature = "std", feature = "palette"))]
+pub mod vision;
 
 #[cfg(feature = "pix")]
-const SIZE_IN_PIXELS: u8 = 30;
+const SIZE_IN_PIXELS: u32 = 30;
 #[cfg(feature = "pix")]
-const SCALING_FACTOR: u8 = 5;
+const SCALING_FACTOR: u32 = 5;
 #[cfg(feature = "pix")]
 const FILTER_TYPE: FilterType = FilterType::Lanczos3;
 #[cfg(feature = "pix")]
@@ -66,12 +113,44 @@ pub const EMPTY_PNG: &[u8] = &[
 ///     assert!(image.height() == size_in_pixels as u32);
 /// }
 /// ```
+///
+/// Zero-sized image could not be generated.
+///
+/// ```
+/// use plot_icon::{generate_png, IdenticonError};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// assert!(matches!(generate_png(alice, 0), Err(IdenticonError::InvalidSize(0))));
+/// ```
 #[cfg(feature = "pix")]
-pub fn generate_png(into_id: &[u8], size_in_pixels: u16) -> Result<Vec<u8>, png::EncodingError> {
+pub fn generate_png(into_id: &[u8], size_in_pixels: u16) -> Result<Vec<u8>, IdenticonError> {
     let colors = colors::get_colors(into_id);
     generate_png_with_colors(colors, size_in_pixels)
 }
 
+/// Polkadot identicon `png` data in `u8` vector format, from SS58 address
+///
+/// Address is decoded and its checksum verified, identicon is generated
+/// for the account bytes, same as in [`generate_png`].
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_png, generate_png_from_ss58};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let content = generate_png_from_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 64).unwrap();
+/// assert!(content == generate_png(alice, 64).unwrap());
+/// ```
+#[cfg(all(feature = "pix", feature = "ss58"))]
+pub fn generate_png_from_ss58(
+    address: &str,
+    size_in_pixels: u16,
+) -> Result<Vec<u8>, IdenticonError> {
+    let (account, _) = input::decode_ss58(address)?;
+    generate_png(&account, size_in_pixels)
+}
+
 /// Polkadot identicon `png` data in `u8` vector format, with given colors
 ///
 /// Makes regular identicons if properly generated color set is plugged in.
@@ -95,9 +174,393 @@ pub fn generate_png(into_id: &[u8], size_in_pixels: u16) -> Result<Vec<u8>, png:
 pub fn generate_png_with_colors(
     colors: [Color; 19],
     size_in_pixels: u16,
-) -> Result<Vec<u8>, png::EncodingError> {
-    let data = circles::calculate_png_data(size_in_pixels, colors);
-    make_png_from_data(&data, size_in_pixels)
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    let data = circles::calculate_png_data(size_in_pixels as u32, colors);
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Polkadot identicon `png` data in `u8` vector format, from `&[u8]` input
+/// slice, with colors and decorations set in the [`Style`]
+///
+/// With default style the image is the same as [`generate_png`] output.
+///
+/// ## Example
+///
+/// Identicon for dark page theme.
+///
+/// ```
+/// use plot_icon::{generate_png, generate_png_with_style, Color, Style};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let dark_grey = Color{red: 48, green: 48, blue: 48, alpha: 255};
+/// let style = Style{disc: dark_grey, blank: dark_grey, ..Style::default()};
+/// let content = generate_png_with_style(alice, 64, &style).unwrap();
+/// assert!(content != generate_png(alice, 64).unwrap());
+/// assert!(generate_png_with_style(alice, 64, &Style::default()).unwrap() == generate_png(alice, 64).unwrap());
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    check_style(style)?;
+    let colors = colors::get_colors_with_style(into_id, style);
+    let data = circles::calculate_png_data_with_style(size_in_pixels as u32, colors, style);
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Anti-aliased polkadot identicon `png` data in `u8` vector format,
+/// from `&[u8]` input slice
+///
+/// Input slice could be of any length, as it gets hashed anyways;
+/// typical input is a public key.
+///
+/// Each pixel gets the colors of the circles in proportion to the pixel area
+/// covered by each circle, so that circle edges are smooth. Image is
+/// generated in one pass, with no resizing, and is suitable for small sizes as well.
+///
+/// ## Example
+///
+/// ```
+/// use image::load_from_memory;
+/// use plot_icon::generate_png_antialiased;
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let size_set = [16, 24, 30, 32, 64];
+/// for size_in_pixels in size_set.into_iter() {
+///     let content = generate_png_antialiased(alice, size_in_pixels).unwrap();
+///     let image = load_from_memory(&content).unwrap();
+///     assert!(image.width() == size_in_pixels as u32);
+/// }
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_antialiased(
+    into_id: &[u8],
+    size_in_pixels: u16,
+) -> Result<Vec<u8>, IdenticonError> {
+    let colors = colors::get_colors(into_id);
+    generate_png_antialiased_with_colors(colors, size_in_pixels)
+}
+
+/// Anti-aliased polkadot identicon `png` data in `u8` vector format,
+/// with given colors
+///
+/// Makes regular identicons if properly generated color set is plugged in.
+/// Also could be used to generate test identicon-like pictures.
+///
+/// Input [`Color`] set is in RGBA format.
+#[cfg(feature = "pix")]
+pub fn generate_png_antialiased_with_colors(
+    colors: [Color; 19],
+    size_in_pixels: u16,
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    let data = circles::calculate_png_data_antialiased(size_in_pixels as u32, colors);
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Anti-aliased polkadot identicon `png` data in `u8` vector format,
+/// from `&[u8]` input slice, with colors and decorations set in the [`Style`]
+///
+/// Circle edges are blended with the background color.
+#[cfg(feature = "pix")]
+pub fn generate_png_antialiased_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    check_style(style)?;
+    let colors = colors::get_colors_with_style(into_id, style);
+    let data =
+        circles::calculate_png_data_antialiased_with_style(size_in_pixels as u32, colors, style);
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Polkadot identicon RGBA8 pixels as [`RgbaImage`], from `&[u8]` input slice
+///
+/// Pixels are the same as in [`generate_png`] output, but are not encoded
+/// into `png`, and could be directly uploaded as a texture.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::generate_rgba;
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let image = generate_rgba(alice, 64).unwrap();
+/// assert!(image.width() == 64);
+/// assert!(image.as_raw().len() == 64 * 64 * 4);
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_rgba(into_id: &[u8], size_in_pixels: u16) -> Result<RgbaImage, IdenticonError> {
+    let colors = colors::get_colors(into_id);
+    generate_rgba_with_colors(colors, size_in_pixels)
+}
+
+/// Polkadot identicon RGBA8 pixels as [`RgbaImage`], with given colors
+///
+/// Input [`Color`] set is in RGBA format.
+#[cfg(feature = "pix")]
+pub fn generate_rgba_with_colors(
+    colors: [Color; 19],
+    size_in_pixels: u16,
+) -> Result<RgbaImage, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    let data = circles::calculate_png_data(size_in_pixels as u32, colors);
+    Ok(
+        RgbaImage::from_raw(size_in_pixels as u32, size_in_pixels as u32, data)
+            .expect("pixel data is generated for exactly this image size"),
+    )
+}
+
+/// Polkadot identicon RGBA8 pixels as [`RgbaImage`], from `&[u8]` input
+/// slice, with colors and decorations set in the [`Style`]
+#[cfg(feature = "pix")]
+pub fn generate_rgba_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Result<RgbaImage, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    check_style(style)?;
+    let colors = colors::get_colors_with_style(into_id, style);
+    let data = circles::calculate_png_data_with_style(size_in_pixels as u32, colors, style);
+    Ok(
+        RgbaImage::from_raw(size_in_pixels as u32, size_in_pixels as u32, data)
+            .expect("pixel data is generated for exactly this image size"),
+    )
+}
+
+/// Anti-aliased polkadot identicon RGBA8 pixels as [`RgbaImage`],
+/// from `&[u8]` input slice
+///
+/// Pixels are the same as in [`generate_png_antialiased`] output,
+/// but are not encoded into `png`.
+#[cfg(feature = "pix")]
+pub fn generate_rgba_antialiased(
+    into_id: &[u8],
+    size_in_pixels: u16,
+) -> Result<RgbaImage, IdenticonError> {
+    let colors = colors::get_colors(into_id);
+    generate_rgba_antialiased_with_colors(colors, size_in_pixels)
+}
+
+/// Anti-aliased polkadot identicon RGBA8 pixels as [`RgbaImage`],
+/// with given colors
+///
+/// Input [`Color`] set is in RGBA format.
+#[cfg(feature = "pix")]
+pub fn generate_rgba_antialiased_with_colors(
+    colors: [Color; 19],
+    size_in_pixels: u16,
+) -> Result<RgbaImage, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    let data = circles::calculate_png_data_antialiased(size_in_pixels as u32, colors);
+    Ok(
+        RgbaImage::from_raw(size_in_pixels as u32, size_in_pixels as u32, data)
+            .expect("pixel data is generated for exactly this image size"),
+    )
+}
+
+/// Anti-aliased polkadot identicon RGBA8 pixels as [`RgbaImage`],
+/// from `&[u8]` input slice, with colors and decorations set in the [`Style`]
+#[cfg(feature = "pix")]
+pub fn generate_rgba_antialiased_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Result<RgbaImage, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    check_style(style)?;
+    let colors = colors::get_colors_with_style(into_id, style);
+    let data =
+        circles::calculate_png_data_antialiased_with_style(size_in_pixels as u32, colors, style);
+    Ok(
+        RgbaImage::from_raw(size_in_pixels as u32, size_in_pixels as u32, data)
+            .expect("pixel data is generated for exactly this image size"),
+    )
+}
+
+/// Write polkadot identicon RGBA8 pixels into caller-supplied buffer,
+/// from `&[u8]` input slice
+///
+/// Stride is the number of bytes between starts of consecutive pixel rows,
+/// it must be at least `size_in_pixels * 4`. Bytes between the row end and
+/// the next row start are not modified.
+///
+/// ## Example
+///
+/// Write identicons side by side into a single texture atlas.
+///
+/// ```
+/// use plot_icon::write_rgba;
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let bob: &[u8] = &[142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97, 54, 147, 201, 18, 144, 156, 178, 38, 170, 71, 148, 242, 106, 72];
+/// let size_in_pixels = 32;
+/// let stride = 2 * size_in_pixels as usize * 4;
+/// let mut atlas = vec![0u8; stride * size_in_pixels as usize];
+/// write_rgba(alice, size_in_pixels, &mut atlas, stride).unwrap();
+/// write_rgba(bob, size_in_pixels, &mut atlas[size_in_pixels as usize * 4..], stride).unwrap();
+/// ```
+///
+/// Buffer must fit all the rows with given stride, including strides so
+/// large that the required length overflows `usize`.
+///
+/// ```
+/// use plot_icon::{write_rgba, IdenticonError};
+///
+/// let mut buffer = [0u8; 64];
+/// assert!(matches!(
+///     write_rgba(&[1, 2, 3], 2, &mut buffer, 60),
+///     Err(IdenticonError::BufferTooSmall { length: 64, required: 68 })
+/// ));
+/// assert!(matches!(
+///     write_rgba(&[1, 2, 3], 2, &mut buffer, usize::MAX),
+///     Err(IdenticonError::BufferTooSmall { length: 64, required: usize::MAX })
+/// ));
+/// ```
+pub fn write_rgba(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    buffer: &mut [u8],
+    stride: usize,
+) -> Result<(), IdenticonError> {
+    let colors = colors::get_colors(into_id);
+    write_rgba_with_colors(colors, size_in_pixels, buffer, stride)
+}
+
+/// Write polkadot identicon RGBA8 pixels into caller-supplied buffer,
+/// with given colors
+///
+/// Input [`Color`] set is in RGBA format.
+pub fn write_rgba_with_colors(
+    colors: [Color; 19],
+    size_in_pixels: u16,
+    buffer: &mut [u8],
+    stride: usize,
+) -> Result<(), IdenticonError> {
+    check_buffer(size_in_pixels, buffer, stride)?;
+    circles::write_png_data(
+        size_in_pixels as u32,
+        colors,
+        circles::Centering::Exact,
+        buffer,
+        stride,
+    );
+    Ok(())
+}
+
+/// Write polkadot identicon RGBA8 pixels into caller-supplied buffer,
+/// from `&[u8]` input slice, with colors and decorations set in the [`Style`]
+///
+/// Stride is the number of bytes between starts of consecutive pixel rows,
+/// it must be at least `size_in_pixels * 4`.
+pub fn write_rgba_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+    buffer: &mut [u8],
+    stride: usize,
+) -> Result<(), IdenticonError> {
+    check_buffer(size_in_pixels, buffer, stride)?;
+    #[cfg(feature = "alloc")]
+    check_style(style)?;
+    circles::write_png_data_with_style(
+        size_in_pixels as u32,
+        colors::get_colors_with_style(into_id, style),
+        circles::Centering::Exact,
+        style,
+        buffer,
+        stride,
+    );
+    Ok(())
+}
+
+/// Write anti-aliased polkadot identicon RGBA8 pixels into caller-supplied
+/// buffer, from `&[u8]` input slice
+///
+/// Stride is the number of bytes between starts of consecutive pixel rows,
+/// it must be at least `size_in_pixels * 4`.
+pub fn write_rgba_antialiased(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    buffer: &mut [u8],
+    stride: usize,
+) -> Result<(), IdenticonError> {
+    let colors = colors::get_colors(into_id);
+    write_rgba_antialiased_with_colors(colors, size_in_pixels, buffer, stride)
+}
+
+/// Write anti-aliased polkadot identicon RGBA8 pixels into caller-supplied
+/// buffer, with given colors
+///
+/// Input [`Color`] set is in RGBA format.
+pub fn write_rgba_antialiased_with_colors(
+    colors: [Color; 19],
+    size_in_pixels: u16,
+    buffer: &mut [u8],
+    stride: usize,
+) -> Result<(), IdenticonError> {
+    check_buffer(size_in_pixels, buffer, stride)?;
+    circles::write_png_data_antialiased(size_in_pixels as u32, colors, buffer, stride);
+    Ok(())
+}
+
+/// Write anti-aliased polkadot identicon RGBA8 pixels into caller-supplied
+/// buffer, from `&[u8]` input slice, with colors and decorations set in the [`Style`]
+///
+/// Stride is the number of bytes between starts of consecutive pixel rows,
+/// it must be at least `size_in_pixels * 4`.
+pub fn write_rgba_antialiased_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+    buffer: &mut [u8],
+    stride: usize,
+) -> Result<(), IdenticonError> {
+    check_buffer(size_in_pixels, buffer, stride)?;
+    #[cfg(feature = "alloc")]
+    check_style(style)?;
+    circles::write_png_data_antialiased_with_style(
+        size_in_pixels as u32,
+        colors::get_colors_with_style(into_id, style),
+        style,
+        buffer,
+        stride,
+    );
+    Ok(())
+}
+
+/// Helper function to check that the buffer with given stride fits the image
+fn check_buffer(size_in_pixels: u16, buffer: &[u8], stride: usize) -> Result<(), IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    let row_len = size_in_pixels as usize * 4;
+    if stride < row_len {
+        return Err(IdenticonError::InvalidStride {
+            stride,
+            minimum: row_len,
+        });
+    }
+    // required length that does not fit into `usize` is reported as `usize::MAX`
+    let required = match size_in_pixels {
+        0 => 0,
+        a => stride
+            .checked_mul(a as usize - 1)
+            .and_then(|b| b.checked_add(row_len))
+            .unwrap_or(usize::MAX),
+    };
+    if buffer.len() < required {
+        return Err(IdenticonError::BufferTooSmall {
+            length: buffer.len(),
+            required,
+        });
+    }
+    Ok(())
 }
 
 /// Data for small-sized identicon `png`, from `&[u8]` input slice,
@@ -106,7 +569,9 @@ pub fn generate_png_with_colors(
 /// Input slice could be of any length, as it gets hashed anyways;
 /// typical input is a public key.
 ///
-/// Function is expected to be used with image size values approximately 100 pix and below.
+/// Function is mostly used with image size values approximately 100 pix and below,
+/// but it accepts `u32` sizes, so that large identicons are generated with the
+/// same downscale quality.
 ///
 /// # Scaling factor
 ///
@@ -130,8 +595,8 @@ pub fn generate_png_with_colors(
 /// ```
 /// For image size 32, the `scaling_factor = 1` results in strongly pixelated image,
 /// it is identical to `generate_png` result.
-/// With `scaling_factor = 2` image is already much less pixelated, off-centering still
-/// visible; off-centering virtually disappears for `scaling factor = 4` and above,
+/// With `scaling_factor = 2` image is already much less pixelated,
+/// and pixelation virtually disappears for `scaling factor = 4` and above,
 /// after `scaling factor = 6` it is quite challenging to find any image differences at all.
 ///
 /// # Filter type
@@ -198,17 +663,152 @@ pub fn generate_png_with_colors(
 /// For now the default scaling factor is selected to be `5`,
 /// the default filter is selected to be `FilterType::Lanczos3`.
 ///
+/// # Large images
+///
+/// Size of the larger image (size multiplied by scaling factor) is checked:
+/// it must not exceed `i32::MAX`, the largest `png` dimension, and its RGBA8
+/// pixel data length must not exceed `isize::MAX` bytes, the largest
+/// allocation on the platform. Otherwise the error is produced. Available
+/// memory is not checked, if the allocation fails, the process is aborted,
+/// as usual for the allocations in Rust.
+///
+/// ```
+/// use image::imageops::FilterType;
+/// use plot_icon::{generate_png_scaled_custom, IdenticonError};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let content = generate_png_scaled_custom(alice, 512, 2, FilterType::Lanczos3).unwrap();
+/// let error = generate_png_scaled_custom(alice, 1 << 20, 1 << 12, FilterType::Lanczos3).unwrap_err();
+/// assert!(matches!(error, IdenticonError::SizeOverflow{..}));
+/// let error = generate_png_scaled_custom(alice, 1 << 16, 1 << 15, FilterType::Lanczos3).unwrap_err();
+/// assert!(matches!(error, IdenticonError::SizeOverflow{..}));
+/// ```
+///
 #[cfg(feature = "pix")]
 pub fn generate_png_scaled_custom(
     into_id: &[u8],
-    size_in_pixels: u8,
-    scaling_factor: u8,
+    size_in_pixels: u32,
+    scaling_factor: u32,
     filter_type: FilterType,
 ) -> Result<Vec<u8>, IdenticonError> {
     let colors = colors::get_colors(into_id);
     generate_png_scaled_custom_with_colors(colors, size_in_pixels, scaling_factor, filter_type)
 }
 
+/// Data for small-sized identicon `png`, from `&[u8]` input slice, with
+/// colors and decorations set in the [`Style`],
+/// larger image is generated first and then scaled down to fit the required size
+///
+/// With default style the image is the same as [`generate_png_scaled_custom`] output.
+#[cfg(feature = "pix")]
+pub fn generate_png_scaled_custom_with_style(
+    into_id: &[u8],
+    size_in_pixels: u32,
+    scaling_factor: u32,
+    filter_type: FilterType,
+    style: &Style,
+) -> Result<Vec<u8>, IdenticonError> {
+    let image_small = generate_rgba_scaled_custom_with_style(
+        into_id,
+        size_in_pixels,
+        scaling_factor,
+        filter_type,
+        style,
+    )?;
+    make_png_from_data(&image_small, size_in_pixels)
+}
+
+/// Data for small-sized identicon `png`, from SS58 address,
+/// larger image is generated first and then scaled down to fit the required size
+///
+/// Address is decoded and its checksum verified, identicon is generated
+/// for the account bytes, same as in [`generate_png_scaled_custom`].
+#[cfg(all(feature = "pix", feature = "ss58"))]
+pub fn generate_png_scaled_custom_from_ss58(
+    address: &str,
+    size_in_pixels: u32,
+    scaling_factor: u32,
+    filter_type: FilterType,
+) -> Result<Vec<u8>, IdenticonError> {
+    let (account, _) = input::decode_ss58(address)?;
+    generate_png_scaled_custom(&account, size_in_pixels, scaling_factor, filter_type)
+}
+
+/// Small-sized identicon RGBA8 pixels as [`RgbaImage`], from `&[u8]` input slice,
+/// larger image is generated first and then scaled down to fit the required size
+///
+/// Pixels are the same as in [`generate_png_scaled_custom`] output,
+/// but are not encoded into `png`.
+///
+/// ## Example
+///
+/// ```
+/// use image::imageops::FilterType;
+/// use plot_icon::generate_rgba_scaled_custom;
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let image = generate_rgba_scaled_custom(alice, 30, 5, FilterType::Lanczos3).unwrap();
+/// assert!(image.width() == 30);
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_rgba_scaled_custom(
+    into_id: &[u8],
+    size_in_pixels: u32,
+    scaling_factor: u32,
+    filter_type: FilterType,
+) -> Result<RgbaImage, IdenticonError> {
+    let colors = colors::get_colors(into_id);
+    generate_rgba_scaled_custom_with_colors(colors, size_in_pixels, scaling_factor, filter_type)
+}
+
+/// Small-sized identicon RGBA8 pixels as [`RgbaImage`], with given colors,
+/// larger image is generated first and then scaled down to fit the required size
+///
+/// Input [`Color`] set is in RGBA format.
+#[cfg(feature = "pix")]
+pub fn generate_rgba_scaled_custom_with_colors(
+    colors: [Color; 19],
+    size_in_pixels: u32,
+    scaling_factor: u32,
+    filter_type: FilterType,
+) -> Result<RgbaImage, IdenticonError> {
+    let size_large = scaled_size(size_in_pixels, scaling_factor)?;
+    let data_large = circles::calculate_png_data(size_large, colors);
+    let image_large = RgbaImage::from_raw(size_large, size_large, data_large)
+        .expect("pixel data is generated for exactly this image size");
+    Ok(resize(
+        &image_large,
+        size_in_pixels,
+        size_in_pixels,
+        filter_type,
+    ))
+}
+
+/// Small-sized identicon RGBA8 pixels as [`RgbaImage`], from `&[u8]` input
+/// slice, with colors and decorations set in the [`Style`],
+/// larger image is generated first and then scaled down to fit the required size
+#[cfg(feature = "pix")]
+pub fn generate_rgba_scaled_custom_with_style(
+    into_id: &[u8],
+    size_in_pixels: u32,
+    scaling_factor: u32,
+    filter_type: FilterType,
+    style: &Style,
+) -> Result<RgbaImage, IdenticonError> {
+    let size_large = scaled_size(size_in_pixels, scaling_factor)?;
+    check_style(style)?;
+    let colors = colors::get_colors_with_style(into_id, style);
+    let data_large = circles::calculate_png_data_with_style(size_large, colors, style);
+    let image_large = RgbaImage::from_raw(size_large, size_large, data_large)
+        .expect("pixel data is generated for exactly this image size");
+    Ok(resize(
+        &image_large,
+        size_in_pixels,
+        size_in_pixels,
+        filter_type,
+    ))
+}
+
 /// Data for small-sized identicon `png`, with given colors,
 /// larger image is generated first and then scaled down to fit the required size
 ///
@@ -237,21 +837,17 @@ pub fn generate_png_scaled_custom(
 #[cfg(feature = "pix")]
 pub fn generate_png_scaled_custom_with_colors(
     colors: [Color; 19],
-    size_in_pixels: u8,
-    scaling_factor: u8,
+    size_in_pixels: u32,
+    scaling_factor: u32,
     filter_type: FilterType,
 ) -> Result<Vec<u8>, IdenticonError> {
-    let data_large =
-        generate_png_with_colors(colors, size_in_pixels as u16 * scaling_factor as u16)
-            .map_err(IdenticonError::Png)?;
-    let image_large = load_from_memory(&data_large).map_err(IdenticonError::Image)?;
-    let image_small = resize(
-        &image_large,
-        size_in_pixels as u32,
-        size_in_pixels as u32,
+    let image_small = generate_rgba_scaled_custom_with_colors(
+        colors,
+        size_in_pixels,
+        scaling_factor,
         filter_type,
-    );
-    make_png_from_data(&image_small, size_in_pixels as u16).map_err(IdenticonError::Png)
+    )?;
+    make_png_from_data(&image_small, size_in_pixels)
 }
 
 /// Data for small-sized identicon `png`, from `&[u8]` input slice,
@@ -280,11 +876,379 @@ pub fn generate_png_scaled_default(into_id: &[u8]) -> Vec<u8> {
     }
 }
 
+/// Data for small-sized identicon `png`, from SS58 address,
+/// with default settings used for Signer app
+///
+/// Fails only if the address could not be decoded. If `png` generation
+/// itself fails, is falls back to transparent 30x30 `png`,
+/// same as [`generate_png_scaled_default`].
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_png_scaled_default_from_ss58, input::InputError, IdenticonError};
+///
+/// let content = generate_png_scaled_default_from_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
+/// let typo = generate_png_scaled_default_from_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ");
+/// assert!(matches!(typo, Err(IdenticonError::Input(InputError::BadChecksum))));
+/// ```
+#[cfg(all(feature = "pix", feature = "ss58"))]
+pub fn generate_png_scaled_default_from_ss58(address: &str) -> Result<Vec<u8>, IdenticonError> {
+    let (account, _) = input::decode_ss58(address)?;
+    Ok(generate_png_scaled_default(&account))
+}
+
+/// Ethereum-style blockies identicon `png` data in `u8` vector format,
+/// from account bytes
+///
+/// Typical input is 20-byte Ethereum-style account, identicon is the same as
+/// the one polkadot.js shows for such accounts. Image is made of 8x8 cell
+/// grid, sizes divisible by 8 produce cells of equal size.
+///
+/// ## Example
+///
+/// ```
+/// use image::load_from_memory;
+/// use plot_icon::generate_png_blockies;
+///
+/// let account: &[u8] = &[242, 79, 243, 169, 207, 4, 199, 29, 188, 148, 208, 181, 102, 247, 162, 123, 148, 86, 108, 172];
+/// let content = generate_png_blockies(account, 64).unwrap();
+/// let image = load_from_memory(&content).unwrap();
+/// assert!(image.width() == 64);
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_blockies(
+    account: &[u8],
+    size_in_pixels: u16,
+) -> Result<Vec<u8>, IdenticonError> {
+    generate_png_blockies_with_style(account, size_in_pixels, &Style::default())
+}
+
+/// Ethereum-style blockies identicon `png` data in `u8` vector format,
+/// from account bytes, with background, selection ring and badge set in
+/// the [`Style`]
+///
+/// Other style settings are for polkadot identicons and are not used here.
+/// With selection ring the blockies are drawn smaller, in the middle of the
+/// image.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_png_blockies, generate_png_blockies_with_style, Style};
+///
+/// let account: &[u8] = &[242, 79, 243, 169, 207, 4, 199, 29, 188, 148, 208, 181, 102, 247, 162, 123, 148, 86, 108, 172];
+/// let content = generate_png_blockies_with_style(account, 64, &Style::default()).unwrap();
+/// assert!(content == generate_png_blockies(account, 64).unwrap());
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_blockies_with_style(
+    account: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    check_style(style)?;
+    let blockies = blockies::get_blockies(account);
+    let data = frame_png_data(size_in_pixels as u32, style, |size| {
+        blockies::calculate_png_data(size, &blockies)
+    });
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Jdenticon (`substrate` theme of polkadot.js) `png` data in `u8` vector
+/// format, from `&[u8]` input slice
+///
+/// Typical input is a public key, it is converted into jdenticon hash same
+/// way as polkadot.js does. Shapes are anti-aliased, background is transparent.
+///
+/// ## Example
+///
+/// ```
+/// use image::load_from_memory;
+/// use plot_icon::generate_png_jdenticon;
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let content = generate_png_jdenticon(alice, 64).unwrap();
+/// let image = load_from_memory(&content).unwrap();
+/// assert!(image.width() == 64);
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_jdenticon(
+    into_id: &[u8],
+    size_in_pixels: u16,
+) -> Result<Vec<u8>, IdenticonError> {
+    generate_png_jdenticon_with_style(into_id, size_in_pixels, &Style::default())
+}
+
+/// Jdenticon (`substrate` theme of polkadot.js) `png` data in `u8` vector
+/// format, from `&[u8]` input slice, with background, selection ring and
+/// badge set in the [`Style`]
+///
+/// Other style settings are for polkadot identicons and are not used here.
+/// With selection ring the jdenticon is drawn for the size left within the
+/// ring, so that its shapes stay aligned with the pixel grid.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_png_jdenticon, generate_png_jdenticon_with_style, Style};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let content = generate_png_jdenticon_with_style(alice, 64, &Style::default()).unwrap();
+/// assert!(content == generate_png_jdenticon(alice, 64).unwrap());
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_jdenticon_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    check_style(style)?;
+    let data = frame_png_data(size_in_pixels as u32, style, |size| {
+        jdenticon::calculate_png_data(&jdenticon::get_jdenticon(into_id, size))
+    });
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Beachball (`beachball` theme of polkadot.js) `png` data in `u8` vector
+/// format, from the seed bytes
+///
+/// Polkadot.js uses the bytes of SS58 address string as seed. Shapes are
+/// anti-aliased and clipped by the icon circle, outside of the circle
+/// the image is transparent.
+///
+/// ## Example
+///
+/// ```
+/// use image::load_from_memory;
+/// use plot_icon::generate_png_beachball;
+///
+/// let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+/// let content = generate_png_beachball(address.as_bytes(), 64).unwrap();
+/// let image = load_from_memory(&content).unwrap();
+/// assert!(image.width() == 64);
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_beachball(seed: &[u8], size_in_pixels: u16) -> Result<Vec<u8>, IdenticonError> {
+    generate_png_beachball_with_style(seed, size_in_pixels, &Style::default())
+}
+
+/// Beachball (`beachball` theme of polkadot.js) `png` data in `u8` vector
+/// format, from the seed bytes, with background, selection ring and badge
+/// set in the [`Style`]
+///
+/// Other style settings are for polkadot identicons and are not used here.
+/// Background fills the image outside of the beachball circle. With
+/// selection ring the beachball is drawn for the size left within the ring.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_png_beachball, generate_png_beachball_with_style, Style};
+///
+/// let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+/// let content = generate_png_beachball_with_style(address.as_bytes(), 64, &Style::default()).unwrap();
+/// assert!(content == generate_png_beachball(address.as_bytes(), 64).unwrap());
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_beachball_with_style(
+    seed: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    check_style(style)?;
+    let data = frame_png_data(size_in_pixels as u32, style, |size| {
+        beachball::calculate_png_data(&beachball::get_beachball(
+            seed,
+            size,
+            beachball::ShapeKind::default(),
+        ))
+    });
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Identicon `png` data in `u8` vector format, in any [`Theme`]
+///
+/// Theme could be selected at runtime with [`theme::IconTheme`].
+///
+/// ## Example
+///
+/// ```
+/// use image::load_from_memory;
+/// use plot_icon::{generate_png_with_theme, theme::IconTheme};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let theme: IconTheme = "substrate".parse().unwrap();
+/// let content = generate_png_with_theme(&theme, alice, 64).unwrap();
+/// let image = load_from_memory(&content).unwrap();
+/// assert!(image.width() == 64);
+/// ```
+#[cfg(feature = "pix")]
+pub fn generate_png_with_theme(
+    theme: &dyn Theme,
+    into_id: &[u8],
+    size_in_pixels: u16,
+) -> Result<Vec<u8>, IdenticonError> {
+    check_size(size_in_pixels as u32)?;
+    theme.check_png()?;
+    let data = theme.calculate_png_data(into_id, size_in_pixels);
+    make_png_from_data(&data, size_in_pixels as u32)
+}
+
+/// Helper function to check that the style could be drawn in `png` and RGBA
+/// images
+#[cfg(feature = "alloc")]
+fn check_style(style: &Style) -> Result<(), IdenticonError> {
+    match style.badge {
+        Some(ref badge) => badge.check_png(),
+        None => Ok(()),
+    }
+}
+
+/// Helper function to check that the image size is acceptable
+fn check_size(size_in_pixels: u32) -> Result<(), IdenticonError> {
+    if size_in_pixels == 0 {
+        return Err(IdenticonError::InvalidSize(size_in_pixels));
+    }
+    Ok(())
+}
+
+/// Helper function to check that the scaling factor is acceptable
+#[cfg(feature = "pix")]
+fn check_scaling_factor(scaling_factor: u32) -> Result<(), IdenticonError> {
+    if scaling_factor == 0 {
+        return Err(IdenticonError::InvalidScalingFactor(scaling_factor));
+    }
+    Ok(())
+}
+
+/// Helper function to calculate the size of larger image in scaled image generation
+///
+/// Checks that both the size and the scaling factor are acceptable, that
+/// the larger image size does not exceed `i32::MAX` (limit of `png` image
+/// dimensions), and that its pixel data length does not exceed `isize::MAX`
+/// (limit of allocation size).
+#[cfg(feature = "pix")]
+fn scaled_size(size_in_pixels: u32, scaling_factor: u32) -> Result<u32, IdenticonError> {
+    check_size(size_in_pixels)?;
+    check_scaling_factor(scaling_factor)?;
+    let overflow = || IdenticonError::SizeOverflow {
+        size_in_pixels,
+        scaling_factor,
+    };
+    let size_large = size_in_pixels
+        .checked_mul(scaling_factor)
+        .filter(|a| *a <= i32::MAX as u32)
+        .ok_or_else(overflow)?;
+    (size_large as usize)
+        .checked_mul(size_large as usize)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .filter(|a| *a <= isize::MAX as usize)
+        .ok_or_else(overflow)?;
+    Ok(size_large)
+}
+
+/// Helper function to calculate selection ring width and gap together, in
+/// units of the image of given size
+#[cfg(any(feature = "pix", feature = "vec"))]
+fn frame_margin(size: f32, style: &Style) -> f32 {
+    style
+        .selection
+        .map(|selection| (selection.width + selection.gap) * size)
+        .unwrap_or(0f32)
+}
+
+/// Helper function to make frame scene for icons of themes other than
+/// polkadot: selection ring if there is one, and transparent circle
+/// inscribed into the icon square, that sets the badge placement
+///
+/// Frame scene view box is `(0, 0, size, size)`, icon square of `inner`
+/// size is in the middle.
+#[cfg(any(feature = "pix", feature = "vec"))]
+fn frame_scene(size: f32, inner: f32, style: &Style) -> Scene {
+    let center = size / 2f32;
+    let mut elements = Vec::with_capacity(2);
+    if let Some(selection) = style.selection {
+        let width = selection.width * size;
+        elements.push(scene::Element::Ring {
+            x_center: center,
+            y_center: center,
+            radius: center - width / 2f32,
+            width,
+            color: selection.color,
+        });
+    }
+    elements.push(scene::Element::Circle {
+        x_center: center,
+        y_center: center,
+        radius: inner / 2f32,
+        color: Color::background(),
+    });
+    Scene {
+        view_box: (0f32, 0f32, size, size),
+        background: None,
+        clip_to_circle: false,
+        elements,
+    }
+}
+
+/// Helper function to draw icon of theme other than polkadot with the
+/// [`Style`] background, selection ring and badge
+///
+/// Icon pixel data is `render`ed for the size left within the selection
+/// ring, ring width and gap together are rounded to whole pixels. Icon is
+/// placed in the middle, over the background and the ring. Default style
+/// leaves the icon pixel data as is.
+#[cfg(feature = "pix")]
+fn frame_png_data(size_in_pixels: u32, style: &Style, render: impl Fn(u32) -> Vec<u8>) -> Vec<u8> {
+    let margin = (math::round(frame_margin(size_in_pixels as f32, style) as f64) as u32)
+        .min((size_in_pixels - 1) / 2);
+    let inner = size_in_pixels - 2 * margin;
+    let icon = render(inner);
+    if margin == 0 && style.background.alpha == 0 && style.badge.is_none() {
+        return icon;
+    }
+    let size = size_in_pixels as usize;
+    let mut data = style.background.to_array().repeat(size * size);
+    let mut frame = frame_scene(size_in_pixels as f32, inner as f32, style);
+    let badge_scene = frame.clone();
+    frame
+        .elements
+        .retain(|element| matches!(element, scene::Element::Ring { .. }));
+    if !frame.elements.is_empty() {
+        let ring = frame.calculate_png_data(size_in_pixels);
+        for (pixel, color) in data.chunks_exact_mut(4).zip(ring.chunks_exact(4)) {
+            if color[3] != 0 {
+                raster::over(pixel, [color[0], color[1], color[2], color[3]]);
+            }
+        }
+    }
+    let (margin, inner) = (margin as usize, inner as usize);
+    for (j, row) in icon.chunks_exact(inner * 4).enumerate() {
+        for (i, color) in row.chunks_exact(4).enumerate() {
+            if color[3] != 0 {
+                let start = ((j + margin) * size + i + margin) * 4;
+                raster::over(
+                    &mut data[start..start + 4],
+                    [color[0], color[1], color[2], color[3]],
+                );
+            }
+        }
+    }
+    if let Some(ref badge) = style.badge {
+        badge::draw_png_data(badge, &badge_scene, size_in_pixels, &mut data, size * 4);
+    }
+    data
+}
+
 /// Helper function to write calculated pixel-by-pixel `png` pixel data in `png` format, header and all
 #[cfg(feature = "pix")]
-fn make_png_from_data(data: &[u8], size_in_pixels: u16) -> Result<Vec<u8>, png::EncodingError> {
+fn make_png_from_data(data: &[u8], size_in_pixels: u32) -> Result<Vec<u8>, IdenticonError> {
     let mut out: Vec<u8> = Vec::new();
-    let mut encoder = png::Encoder::new(&mut out, size_in_pixels as u32, size_in_pixels as u32);
+    let mut encoder = png::Encoder::new(&mut out, size_in_pixels, size_in_pixels);
     encoder.set_color(png::ColorType::Rgba);
     encoder.set_depth(png::BitDepth::Eight);
     let mut writer = encoder.write_header()?;
@@ -293,43 +1257,6 @@ fn make_png_from_data(data: &[u8], size_in_pixels: u16) -> Result<Vec<u8>, png::
     Ok(out)
 }
 
-/// Errors in `png` identicon generation
-#[derive(Debug)]
-#[cfg(feature = "pix")]
-pub enum IdenticonError {
-    /// [`png::EncodingError`](https://docs.rs/png/latest/png/enum.EncodingError.html)
-    ///
-    /// From `png` crate, could appear on writing the pixel data into `png`,
-    /// generally should not happen, since the `png` parameters are matching the pixel data generated
-    Png(png::EncodingError),
-    /// [`image::ImageError`](https://docs.rs/image/latest/image/error/enum.ImageError.html)
-    ///
-    /// From `image` crate, could appear on loading freshly made `png` into `DynamicImage`,
-    /// very unlikely
-    Image(image::ImageError),
-}
-
-#[cfg(feature = "pix")]
-impl IdenticonError {
-    /// displaying error text
-    pub fn show(&self) -> String {
-        match &self {
-            IdenticonError::Png(e) => format!("Error encoding data into png format: {}", e),
-            IdenticonError::Image(e) => format!(
-                "Error transforming png data into DynamicImage format: {}",
-                e
-            ),
-        }
-    }
-}
-
-#[cfg(feature = "pix")]
-impl std::fmt::Display for IdenticonError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.show())
-    }
-}
-
 /// Identicon [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
 /// data, from `&[u8]` input slice
 ///
@@ -353,6 +1280,28 @@ pub fn generate_svg(into_id: &[u8]) -> Document {
     generate_svg_with_colors(colors)
 }
 
+/// Identicon [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from SS58 address
+///
+/// Address is decoded and its checksum verified, identicon is generated
+/// for the account bytes, same as in [`generate_svg`].
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::generate_svg_from_ss58;
+///
+/// let svg_document = generate_svg_from_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
+/// let mut svg_expected_content = String::new();
+/// svg::open("test_pics/alice.svg", &mut svg_expected_content).unwrap();
+/// assert!(svg_document.to_string() == svg_expected_content);
+/// ```
+#[cfg(all(feature = "vec", feature = "ss58"))]
+pub fn generate_svg_from_ss58(address: &str) -> Result<Document, IdenticonError> {
+    let (account, _) = input::decode_ss58(address)?;
+    Ok(generate_svg(&account))
+}
+
 /// Identicon [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
 /// data, with given colors
 ///
@@ -371,11 +1320,275 @@ pub fn generate_svg(into_id: &[u8]) -> Document {
 /// ```
 #[cfg(feature = "vec")]
 pub fn generate_svg_with_colors(colors: [Color; 19]) -> Document {
+    make_svg_document(colors, &Style::default())
+}
+
+/// Identicon [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from `&[u8]` input slice, with colors and decorations set in the [`Style`]
+///
+/// Non-transparent background is drawn as a rectangle filling the viewBox.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_svg, generate_svg_with_style, Color, Style};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let style = Style{background: Color{red: 0, green: 0, blue: 0, alpha: 255}, ..Style::default()};
+/// let svg_document = generate_svg_with_style(alice, &style);
+/// assert!(svg_document.to_string().contains("<rect fill=\"#000000\""));
+/// assert!(generate_svg_with_style(alice, &Style::default()).to_string() == generate_svg(alice).to_string());
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_with_style(into_id: &[u8], style: &Style) -> Document {
+    let colors = colors::get_colors_with_style(into_id, style);
+    make_svg_document(colors, style)
+}
+
+/// Helper function to make polkadot identicon `svg` document
+#[cfg(feature = "vec")]
+fn make_svg_document(colors: [Color; 19], style: &Style) -> Document {
     let unit = 10; // svg is vector format, the unit size is arbitrary, and does not influence the outcome
     let mut document = Document::new().set("viewBox", (-unit, -unit, 2 * unit, 2 * unit));
-    let data = circles::calculate_svg_data(unit as f32, colors);
+    let view_box = (
+        -unit as f32,
+        -unit as f32,
+        2f32 * unit as f32,
+        2f32 * unit as f32,
+    );
+    if let Some(rectangle) = scene::svg_background(view_box, style.background) {
+        document = document.add(rectangle);
+    }
+    let data = circles::calculate_svg_data_with_style(unit as f32, colors, style);
     for x in data.into_iter() {
         document = document.add(x);
     }
+    if let Some(ref badge) = style.badge {
+        let scene = circles::calculate_scene_with_style(unit as f32, colors, style);
+        for x in badge::calculate_svg_data(badge, &scene).into_iter() {
+            document = document.add(x);
+        }
+    }
+    document
+}
+
+/// Helper function to add icon `svg` elements of theme other than polkadot
+/// into the document with view box `(0, 0, size, size)`, with the [`Style`]
+/// background, selection ring and badge
+///
+/// With selection ring the icon elements are grouped and scaled down into
+/// the ring. Default style adds the icon elements as is.
+#[cfg(feature = "vec")]
+fn make_framed_svg_document<T: Into<Box<dyn svg::Node>>>(
+    mut document: Document,
+    size: f32,
+    data: Vec<T>,
+    style: &Style,
+) -> Document {
+    let margin = frame_margin(size, style).min(size / 2f32);
+    let inner = size - 2f32 * margin;
+    if let Some(rectangle) = scene::svg_background((0f32, 0f32, size, size), style.background) {
+        document = document.add(rectangle);
+    }
+    let frame = frame_scene(size, inner, style);
+    for element in frame.elements.iter() {
+        if let scene::Element::Ring { .. } = element {
+            document = document.add(scene::svg_circle(element).expect("ring is a circle"));
+        }
+    }
+    if margin == 0f32 {
+        for x in data.into_iter() {
+            document = document.add(x);
+        }
+    } else {
+        let mut group = svg::node::element::Group::new().set(
+            "transform",
+            format!("translate({} {}) scale({})", margin, margin, inner / size),
+        );
+        for x in data.into_iter() {
+            group = group.add(x);
+        }
+        document = document.add(group);
+    }
+    if let Some(ref badge) = style.badge {
+        for x in badge::calculate_svg_data(badge, &frame).into_iter() {
+            document = document.add(x);
+        }
+    }
     document
 }
+
+/// Ethereum-style blockies identicon
+/// [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from account bytes
+///
+/// Typical input is 20-byte Ethereum-style account.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::generate_svg_blockies;
+///
+/// let account: &[u8] = &[242, 79, 243, 169, 207, 4, 199, 29, 188, 148, 208, 181, 102, 247, 162, 123, 148, 86, 108, 172];
+/// let svg_document = generate_svg_blockies(account);
+/// assert!(svg_document.to_string().contains("viewBox=\"0 0 8 8\""));
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_blockies(account: &[u8]) -> Document {
+    generate_svg_blockies_with_style(account, &Style::default())
+}
+
+/// Ethereum-style blockies identicon
+/// [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from account bytes, with background, selection ring and badge set
+/// in the [`Style`]
+///
+/// Other style settings are for polkadot identicons and are not used here.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_svg_blockies, generate_svg_blockies_with_style, Style};
+///
+/// let account: &[u8] = &[242, 79, 243, 169, 207, 4, 199, 29, 188, 148, 208, 181, 102, 247, 162, 123, 148, 86, 108, 172];
+/// let svg_document = generate_svg_blockies_with_style(account, &Style::default());
+/// assert!(svg_document.to_string() == generate_svg_blockies(account).to_string());
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_blockies_with_style(account: &[u8], style: &Style) -> Document {
+    let grid_size = blockies::GRID_SIZE;
+    let document = Document::new()
+        .set("viewBox", (0, 0, grid_size, grid_size))
+        .set("shape-rendering", "crispEdges");
+    let data = blockies::calculate_svg_data(&blockies::get_blockies(account));
+    make_framed_svg_document(document, grid_size as f32, data, style)
+}
+
+/// Jdenticon (`substrate` theme of polkadot.js)
+/// [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from `&[u8]` input slice
+///
+/// Jdenticon shapes are aligned with the pixel grid of the icon size,
+/// so the size is needed for `svg` as well. Paths are the same as in
+/// javascript jdenticon `svg` output; for exact javascript markup use
+/// [`jdenticon::to_svg_string`].
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::generate_svg_jdenticon;
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let svg_document = generate_svg_jdenticon(alice, 64);
+/// assert!(svg_document.to_string().contains("fill=\"#66cc69\""));
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_jdenticon(into_id: &[u8], size_in_pixels: u16) -> Document {
+    generate_svg_jdenticon_with_style(into_id, size_in_pixels, &Style::default())
+}
+
+/// Jdenticon (`substrate` theme of polkadot.js)
+/// [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from `&[u8]` input slice, with background, selection ring and
+/// badge set in the [`Style`]
+///
+/// Other style settings are for polkadot identicons and are not used here.
+/// With selection ring the jdenticon shapes are scaled down into the ring,
+/// and stay aligned with the pixel grid of the full size.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_svg_jdenticon, generate_svg_jdenticon_with_style, Style};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let svg_document = generate_svg_jdenticon_with_style(alice, 64, &Style::default());
+/// assert!(svg_document.to_string() == generate_svg_jdenticon(alice, 64).to_string());
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_jdenticon_with_style(
+    into_id: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Document {
+    let jdenticon = jdenticon::get_jdenticon(into_id, size_in_pixels as u32);
+    let document = Document::new()
+        .set("width", size_in_pixels)
+        .set("height", size_in_pixels)
+        .set("viewBox", (0, 0, size_in_pixels, size_in_pixels));
+    let data = jdenticon::calculate_svg_data(&jdenticon);
+    make_framed_svg_document(document, size_in_pixels as f32, data, style)
+}
+
+/// Beachball (`beachball` theme of polkadot.js)
+/// [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from the seed bytes
+///
+/// Polkadot.js uses the bytes of SS58 address string as seed. Shape
+/// positions are rounded same way as in javascript, for given icon size.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::generate_svg_beachball;
+///
+/// let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+/// let svg_document = generate_svg_beachball(address.as_bytes(), 64);
+/// assert!(svg_document.to_string().contains("fill=\"hsl(185.7, 93.7%, 18.8%)\""));
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_beachball(seed: &[u8], size_in_pixels: u16) -> Document {
+    generate_svg_beachball_with_style(seed, size_in_pixels, &Style::default())
+}
+
+/// Beachball (`beachball` theme of polkadot.js)
+/// [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, from the seed bytes, with background, selection ring and badge set
+/// in the [`Style`]
+///
+/// Other style settings are for polkadot identicons and are not used here.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_svg_beachball, generate_svg_beachball_with_style, Style};
+///
+/// let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+/// let svg_document = generate_svg_beachball_with_style(address.as_bytes(), 64, &Style::default());
+/// assert!(svg_document.to_string() == generate_svg_beachball(address.as_bytes(), 64).to_string());
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_beachball_with_style(
+    seed: &[u8],
+    size_in_pixels: u16,
+    style: &Style,
+) -> Document {
+    let beachball =
+        beachball::get_beachball(seed, size_in_pixels as u32, beachball::ShapeKind::default());
+    let document = Document::new()
+        .set("width", size_in_pixels)
+        .set("height", size_in_pixels)
+        .set("viewBox", (0, 0, size_in_pixels, size_in_pixels));
+    let data = beachball::calculate_svg_data(&beachball, "beachball-clip");
+    make_framed_svg_document(document, size_in_pixels as f32, data, style)
+}
+
+/// Identicon [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+/// data, in any [`Theme`]
+///
+/// Some themes align shapes with the pixel grid, so the size is needed
+/// for `svg` as well.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{generate_svg, generate_svg_with_theme, theme::IconTheme};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let svg_document = generate_svg_with_theme(&IconTheme::Polkadot, alice, 64);
+/// assert!(svg_document.to_string() == generate_svg(alice).to_string());
+/// ```
+#[cfg(feature = "vec")]
+pub fn generate_svg_with_theme(theme: &dyn Theme, into_id: &[u8], size_in_pixels: u16) -> Document {
+    theme.svg(into_id, size_in_pixels)
+}
@@ -0,0 +1,88 @@
+//! Floating point functions, that are not available in `core`
+//!
+//! With `std` feature the standard library methods are used, otherwise the
+//! same functions from `libm`. Square root and rounding are exact in both,
+//! trigonometry could differ in the last bit.
+
+// without `alloc` only the circle positions are calculated
+#![cfg_attr(not(feature = "alloc"), allow(dead_code))]
+
+#[cfg(feature = "std")]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn sqrtf(x: f32) -> f32 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sqrtf(x: f32) -> f32 {
+    libm::sqrtf(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn asin(x: f64) -> f64 {
+    x.asin()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn asin(x: f64) -> f64 {
+    libm::asin(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn sin(x: f64) -> f64 {
+    x.sin()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sin(x: f64) -> f64 {
+    libm::sin(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn cos(x: f64) -> f64 {
+    x.cos()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn cos(x: f64) -> f64 {
+    libm::cos(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn ceil(x: f64) -> f64 {
+    x.ceil()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn ceil(x: f64) -> f64 {
+    libm::ceil(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn round(x: f64) -> f64 {
+    x.round()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn round(x: f64) -> f64 {
+    libm::round(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn hypotf(x: f32, y: f32) -> f32 {
+    x.hypot(y)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn hypotf(x: f32, y: f32) -> f32 {
+    libm::hypotf(x, y)
+}
@@ -0,0 +1,200 @@
+//! Rasterization of filled paths into RGBA8 pixel data
+//!
+//! Used for the identicon themes, that are defined as vector paths
+//! (with straight segments only) rather than as circle sets.
+//!
+//! Paths are filled with nonzero winding rule, same as `svg` default,
+//! so that holes are made by the figures going in opposite direction.
+//! Pixel coverage is calculated with `SAMPLES`x`SAMPLES` regular grid of
+//! sample points, shapes with pixel-aligned edges are rendered exactly.
+use alloc::{vec, vec::Vec};
+
+use crate::colors::Color;
+use crate::math;
+
+/// Number of sample rows and sample columns per pixel
+const SAMPLES: usize = 16;
+
+/// Closed polygon, in pixel coordinates
+pub(crate) type Polygon = Vec<(f64, f64)>;
+
+/// Polygon approximating the ellipse with axes along the coordinate axes,
+/// in pixel coordinates
+pub(crate) fn ellipse(x_center: f64, y_center: f64, x_radius: f64, y_radius: f64) -> Polygon {
+    let segments = math::ceil(4f64 * x_radius.max(y_radius)).max(32f64) as usize;
+    (0..segments)
+        .map(|i| {
+            let angle = 2f64 * core::f64::consts::PI * i as f64 / segments as f64;
+            (
+                x_center + x_radius * math::cos(angle),
+                y_center + y_radius * math::sin(angle),
+            )
+        })
+        .collect()
+}
+
+/// Calculate pixel coverage of the path made of the polygons, for square
+/// image `size_in_pixels` wide
+///
+/// Coverage for each pixel is the number of covered sample points,
+/// from 0 to `SAMPLES * SAMPLES`.
+pub(crate) fn coverage(size_in_pixels: u32, polygons: &[Polygon]) -> Vec<u16> {
+    let size = size_in_pixels as usize;
+    let mut out = vec![0u16; size * size];
+    let edges: Vec<((f64, f64), (f64, f64))> = polygons
+        .iter()
+        .flat_map(|polygon| {
+            polygon
+                .iter()
+                .zip(polygon.iter().cycle().skip(1))
+                .map(|(a, b)| (*a, *b))
+        })
+        .filter(|(a, b)| a.1 != b.1)
+        .collect();
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+    let samples_in_row = (size * SAMPLES) as f64;
+    let first_sample =
+        |x: f64| (math::ceil(x * SAMPLES as f64 - 0.5).max(0f64)).min(samples_in_row) as usize;
+    for row in 0..size * SAMPLES {
+        let y = (row as f64 + 0.5) / SAMPLES as f64;
+        crossings.clear();
+        for (a, b) in edges.iter() {
+            let (top, bottom, direction) = if a.1 < b.1 { (a, b, 1) } else { (b, a, -1) };
+            if top.1 <= y && y < bottom.1 {
+                let x = top.0 + (y - top.1) * (bottom.0 - top.0) / (bottom.1 - top.1);
+                crossings.push((x, direction));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let pixel_row = &mut out[(row / SAMPLES) * size..(row / SAMPLES + 1) * size];
+        let mut winding = 0;
+        for i in 0..crossings.len() {
+            winding += crossings[i].1;
+            if winding != 0 && i + 1 < crossings.len() {
+                let start = first_sample(crossings[i].0);
+                let end = first_sample(crossings[i + 1].0);
+                add_span(pixel_row, start, end);
+            }
+        }
+    }
+    out
+}
+
+/// Add span of sample points `[start, end)` into the pixel row coverage
+fn add_span(pixel_row: &mut [u16], start: usize, end: usize) {
+    let mut sample = start;
+    while sample < end {
+        let pixel = sample / SAMPLES;
+        let next = ((pixel + 1) * SAMPLES).min(end);
+        pixel_row[pixel] += (next - sample) as u16;
+        sample = next;
+    }
+}
+
+/// Coverage of the intersection of two paths
+///
+/// Pixels partially covered by both paths get the product of coverages,
+/// as if the paths were independent within the pixel.
+pub(crate) fn intersect(first: &[u16], second: &[u16]) -> Vec<u16> {
+    let full = (SAMPLES * SAMPLES) as u32;
+    first
+        .iter()
+        .zip(second.iter())
+        .map(|(a, b)| ((*a as u32 * *b as u32 + full / 2) / full) as u16)
+        .collect()
+}
+
+/// Composite the color with given pixel coverage over the RGBA8 pixel data
+pub(crate) fn composite(data: &mut [u8], coverage: &[u16], color: Color) {
+    let full = (SAMPLES * SAMPLES) as f64;
+    for (pixel, covered) in data.chunks_exact_mut(4).zip(coverage.iter()) {
+        if *covered == 0 {
+            continue;
+        }
+        let alpha_source = color.alpha as f64 / 255f64 * (*covered as f64) / full;
+        let alpha_destination = pixel[3] as f64 / 255f64 * (1f64 - alpha_source);
+        let alpha = alpha_source + alpha_destination;
+        if alpha == 0f64 {
+            continue;
+        }
+        for (channel, source) in pixel.iter_mut().zip([color.red, color.green, color.blue]) {
+            *channel = math::round(
+                (source as f64 * alpha_source + *channel as f64 * alpha_destination) / alpha,
+            ) as u8;
+        }
+        pixel[3] = math::round(alpha * 255f64) as u8;
+    }
+}
+
+/// Composite RGBA8 color over the RGBA8 pixel, both with straight alpha
+pub(crate) fn over(pixel: &mut [u8], color: [u8; 4]) {
+    let alpha_source = color[3] as f64 / 255f64;
+    let alpha_destination = pixel[3] as f64 / 255f64 * (1f64 - alpha_source);
+    let alpha = alpha_source + alpha_destination;
+    if alpha == 0f64 {
+        return;
+    }
+    for (channel, source) in pixel.iter_mut().zip(color) {
+        *channel = math::round(
+            (source as f64 * alpha_source + *channel as f64 * alpha_destination) / alpha,
+        ) as u8;
+    }
+    pixel[3] = math::round(alpha * 255f64) as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f64, y: f64, side: f64) -> Polygon {
+        vec![(x, y), (x + side, y), (x + side, y + side), (x, y + side)]
+    }
+
+    #[test]
+    fn aligned_square_is_exact() {
+        let coverage = coverage(8, &[square(2f64, 3f64, 4f64)]);
+        for y in 0..8 {
+            for x in 0..8 {
+                let expected = if (2..6).contains(&x) && (3..7).contains(&y) {
+                    (SAMPLES * SAMPLES) as u16
+                } else {
+                    0
+                };
+                assert_eq!(coverage[y * 8 + x], expected, "pixel {}, {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn half_pixel_edge() {
+        let coverage = coverage(4, &[square(0.5f64, 0.5f64, 3f64)]);
+        assert_eq!(coverage[0], (SAMPLES * SAMPLES / 4) as u16);
+        assert_eq!(coverage[1], (SAMPLES * SAMPLES / 2) as u16);
+        assert_eq!(coverage[5], (SAMPLES * SAMPLES) as u16);
+    }
+
+    #[test]
+    fn reversed_polygon_makes_hole() {
+        let mut hole = square(2f64, 2f64, 4f64);
+        hole.reverse();
+        let coverage = coverage(8, &[square(0f64, 0f64, 8f64), hole]);
+        assert_eq!(coverage[0], (SAMPLES * SAMPLES) as u16);
+        assert_eq!(coverage[3 * 8 + 3], 0);
+        // same direction polygons do not make holes
+        let coverage = super::coverage(8, &[square(0f64, 0f64, 8f64), square(2f64, 2f64, 4f64)]);
+        assert_eq!(coverage[3 * 8 + 3], (SAMPLES * SAMPLES) as u16);
+    }
+
+    #[test]
+    fn composite_over_transparent() {
+        let mut data = vec![0u8; 8];
+        let color = Color {
+            red: 200,
+            green: 100,
+            blue: 50,
+            alpha: 255,
+        };
+        composite(&mut data, &[(SAMPLES * SAMPLES) as u16, 0], color);
+        assert_eq!(data, vec![200, 100, 50, 255, 0, 0, 0, 0]);
+    }
+}
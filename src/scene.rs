@@ -0,0 +1,340 @@
+//! Renderer-independent description of identicon geometry
+//!
+//! [`Scene`] lists filled shapes in layer order, in scene coordinates.
+//! Identicon themes (see [`crate::theme`]) produce scenes, that could be
+//! written into `svg` document, rasterized into RGBA8 pixels, or rendered
+//! by any other engine.
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "vec")]
+use svg::{node::element, Document};
+
+use crate::colors::Color;
+
+/// Filled shape of the scene
+#[derive(Clone, Debug, PartialEq)]
+pub enum Element {
+    /// Circle, set by its center and radius
+    Circle {
+        x_center: f32,
+        y_center: f32,
+        radius: f32,
+        color: Color,
+    },
+    /// Circle outline, same as `svg` circle stroke: band of given width,
+    /// centered on the circle with given radius
+    Ring {
+        x_center: f32,
+        y_center: f32,
+        radius: f32,
+        width: f32,
+        color: Color,
+    },
+    /// Closed polygons filled together, with nonzero winding rule
+    ///
+    /// Polygons going in opposite directions make holes.
+    Path {
+        polygons: Vec<Vec<(f32, f32)>>,
+        color: Color,
+    },
+}
+
+impl Element {
+    /// Element color
+    pub fn color(&self) -> Color {
+        match self {
+            Element::Circle { color, .. } => *color,
+            Element::Ring { color, .. } => *color,
+            Element::Path { color, .. } => *color,
+        }
+    }
+}
+
+/// Identicon geometry: shapes in layer order, from bottom to top
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scene {
+    /// Visible area, `(min_x, min_y, width, height)`, same as `svg` viewBox
+    pub view_box: (f32, f32, f32, f32),
+    /// Fill of the whole view box under the elements, not clipped;
+    /// `None` leaves uncovered pixels transparent black
+    pub background: Option<Color>,
+    /// All elements are clipped by the circle inscribed into the view box
+    pub clip_to_circle: bool,
+    /// Elements in layer order, from bottom to top
+    pub elements: Vec<Element>,
+}
+
+impl Scene {
+    /// Calculate anti-aliased RGBA8 pixel data for the scene, with the view
+    /// box stretched over the square image `size_in_pixels` wide
+    ///
+    /// Circles and rings are approximated by polygons, pixels not covered by any
+    /// element get the background color, or are transparent if there is no
+    /// background.
+    pub fn calculate_png_data(&self, size_in_pixels: u32) -> Vec<u8> {
+        let (min_x, min_y, width, height) = self.view_box;
+        let scale_x = size_in_pixels as f64 / width as f64;
+        let scale_y = size_in_pixels as f64 / height as f64;
+        let to_pixels =
+            |(x, y): (f64, f64)| ((x - min_x as f64) * scale_x, (y - min_y as f64) * scale_y);
+        let clip = if self.clip_to_circle {
+            let outline = crate::raster::ellipse(
+                size_in_pixels as f64 / 2f64,
+                size_in_pixels as f64 / 2f64,
+                size_in_pixels as f64 / 2f64,
+                size_in_pixels as f64 / 2f64,
+            );
+            Some(crate::raster::coverage(size_in_pixels, &[outline]))
+        } else {
+            None
+        };
+        let mut data = vec![0u8; size_in_pixels as usize * size_in_pixels as usize * 4];
+        if let Some(background) = self.background {
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.copy_from_slice(&background.to_array());
+            }
+        }
+        for element in self.elements.iter() {
+            let polygons: Vec<crate::raster::Polygon> = match element {
+                Element::Circle {
+                    x_center,
+                    y_center,
+                    radius,
+                    ..
+                } => {
+                    let (x, y) = to_pixels((*x_center as f64, *y_center as f64));
+                    vec![crate::raster::ellipse(
+                        x,
+                        y,
+                        *radius as f64 * scale_x,
+                        *radius as f64 * scale_y,
+                    )]
+                }
+                Element::Ring {
+                    x_center,
+                    y_center,
+                    radius,
+                    width,
+                    ..
+                } => {
+                    let (x, y) = to_pixels((*x_center as f64, *y_center as f64));
+                    let outer = (*radius + *width / 2f32) as f64;
+                    let inner = (*radius - *width / 2f32).max(0f32) as f64;
+                    let mut hole = crate::raster::ellipse(x, y, inner * scale_x, inner * scale_y);
+                    // opposite direction makes the hole
+                    hole.reverse();
+                    vec![
+                        crate::raster::ellipse(x, y, outer * scale_x, outer * scale_y),
+                        hole,
+                    ]
+                }
+                Element::Path { polygons, .. } => polygons
+                    .iter()
+                    .map(|polygon| {
+                        polygon
+                            .iter()
+                            .map(|(x, y)| to_pixels((*x as f64, *y as f64)))
+                            .collect()
+                    })
+                    .collect(),
+            };
+            let mut coverage = crate::raster::coverage(size_in_pixels, &polygons);
+            if let Some(ref clip) = clip {
+                coverage = crate::raster::intersect(&coverage, clip);
+            }
+            crate::raster::composite(&mut data, &coverage, element.color());
+        }
+        data
+    }
+
+    /// Write the scene into [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+    ///
+    /// Document has viewBox of the scene and no set size. If the scene is
+    /// clipped, clip path gets identifier `clip_id`, that should be unique
+    /// within the page the document is placed into.
+    #[cfg(feature = "vec")]
+    pub fn to_svg(&self, clip_id: &str) -> Document {
+        let (min_x, min_y, width, height) = self.view_box;
+        let mut document = Document::new().set("viewBox", (min_x, min_y, width, height));
+        if let Some(rectangle) = self
+            .background
+            .and_then(|color| svg_background(self.view_box, color))
+        {
+            document = document.add(rectangle);
+        }
+        let data = self.calculate_svg_data();
+        if self.clip_to_circle {
+            let clip_path = element::ClipPath::new().set("id", clip_id).add(
+                element::Ellipse::new()
+                    .set("cx", min_x + width / 2f32)
+                    .set("cy", min_y + height / 2f32)
+                    .set("rx", width / 2f32)
+                    .set("ry", height / 2f32),
+            );
+            let mut group = element::Group::new().set("clip-path", format!("url(#{})", clip_id));
+            for x in data.into_iter() {
+                group = group.add(x);
+            }
+            document = document
+                .add(element::Definitions::new().add(clip_path))
+                .add(group);
+        } else {
+            for x in data.into_iter() {
+                document = document.add(x);
+            }
+        }
+        document
+    }
+
+    /// Calculate `svg` elements, one for each scene element, with no clipping
+    /// and no background
+    #[cfg(feature = "vec")]
+    pub fn calculate_svg_data(&self) -> Vec<Box<dyn svg::Node>> {
+        let mut out: Vec<Box<dyn svg::Node>> = Vec::with_capacity(self.elements.len());
+        for x in self.elements.iter() {
+            match x {
+                Element::Circle { .. } | Element::Ring { .. } => {
+                    out.extend(svg_circle(x).map(|circle| Box::new(circle) as Box<dyn svg::Node>))
+                }
+                Element::Path { polygons, color } => {
+                    let mut d = String::new();
+                    for polygon in polygons.iter() {
+                        for (i, (x, y)) in polygon.iter().enumerate() {
+                            let command = if i == 0 { "M" } else { "L" };
+                            d.push_str(&format!("{}{} {}", command, x, y));
+                        }
+                        d.push('Z');
+                    }
+                    out.push(Box::new(
+                        element::Path::new().set("fill", color.to_hex()).set("d", d),
+                    ));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// `svg` circle for the circle or ring element, `None` for other elements
+#[cfg(feature = "vec")]
+pub(crate) fn svg_circle(element: &Element) -> Option<element::Circle> {
+    match element {
+        Element::Circle {
+            x_center,
+            y_center,
+            radius,
+            color,
+        } => Some(
+            element::Circle::new()
+                .set("cx", *x_center)
+                .set("cy", *y_center)
+                .set("r", *radius)
+                .set("fill", color.to_hex())
+                .set("stroke", "none"),
+        ),
+        Element::Ring {
+            x_center,
+            y_center,
+            radius,
+            width,
+            color,
+        } => Some(
+            element::Circle::new()
+                .set("cx", *x_center)
+                .set("cy", *y_center)
+                .set("r", *radius)
+                .set("fill", "none")
+                .set("stroke", color.to_hex())
+                .set("stroke-width", *width),
+        ),
+        Element::Path { .. } => None,
+    }
+}
+
+/// `svg` rectangle filling the view box with the background color,
+/// `None` for transparent background
+///
+/// Partially transparent background gets `fill-opacity`.
+#[cfg(feature = "vec")]
+pub(crate) fn svg_background(
+    (min_x, min_y, width, height): (f32, f32, f32, f32),
+    color: Color,
+) -> Option<element::Rectangle> {
+    if color.alpha == 0 {
+        return None;
+    }
+    let mut rectangle = element::Rectangle::new()
+        .set("x", min_x)
+        .set("y", min_y)
+        .set("width", width)
+        .set("height", height)
+        .set("fill", color.to_hex());
+    if color.alpha < 255 {
+        rectangle = rectangle.set("fill-opacity", color.alpha as f32 / 255f32);
+    }
+    Some(rectangle)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn square_scene(clip_to_circle: bool) -> Scene {
+        Scene {
+            view_box: (0f32, 0f32, 4f32, 4f32),
+            background: None,
+            clip_to_circle,
+            elements: vec![Element::Path {
+                polygons: vec![vec![(0f32, 0f32), (4f32, 0f32), (4f32, 4f32), (0f32, 4f32)]],
+                color: Color::foreground(),
+            }],
+        }
+    }
+
+    #[test]
+    fn square_fills_image() {
+        let data = square_scene(false).calculate_png_data(8);
+        assert!(data
+            .chunks_exact(4)
+            .all(|pixel| pixel == Color::foreground().to_array()));
+    }
+
+    #[test]
+    fn clipped_corners_are_transparent() {
+        let data = square_scene(true).calculate_png_data(8);
+        assert_eq!(data[3], 0);
+        let center = (4 * 8 + 4) * 4;
+        assert_eq!(
+            &data[center..center + 4],
+            &Color::foreground().to_array()[..]
+        );
+    }
+
+    #[test]
+    fn ring_has_hole() {
+        let scene = Scene {
+            view_box: (0f32, 0f32, 8f32, 8f32),
+            background: None,
+            clip_to_circle: false,
+            elements: vec![Element::Ring {
+                x_center: 4f32,
+                y_center: 4f32,
+                radius: 3f32,
+                width: 2f32,
+                color: Color::foreground(),
+            }],
+        };
+        let data = scene.calculate_png_data(8);
+        let pixel = |x: usize, y: usize| data[(y * 8 + x) * 4 + 3];
+        assert_eq!(pixel(3, 3), 0);
+        assert_eq!(pixel(1, 4), 255);
+        assert_eq!(pixel(0, 0), 0);
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn svg_path_data() {
+        let document = square_scene(true).to_svg("clip").to_string();
+        assert!(document.contains("d=\"M0 0L4 0L4 4L0 4Z\""));
+        assert!(document.contains("clip-path=\"url(#clip)\""));
+    }
+}
@@ -0,0 +1,269 @@
+//! Perceptual similarity of identicons, for phishing detection
+//!
+//! Attackers could generate addresses with identicons looking like the
+//! identicon of the victim address. Here the identicons are compared by
+//! their colors: for each circle position, the difference of the colors is
+//! calculated with CIEDE2000 formula in CIE L\*a\*b\* color space, and the
+//! differences are averaged with weights of circle positions.
+//!
+//! CIEDE2000 difference of about 2 is just noticeable, identicons with
+//! unrelated colors are typically 30 and more apart.
+use palette::{ColorDifference, FromColor, Lab, Srgb};
+
+use crate::circles::circle_ring;
+use crate::colors::{get_colors, Color};
+
+/// Identicons closer than this are considered confusable by default
+///
+/// Among identicons of random inputs, less than one pair in 100000 is
+/// this close: 2 of about 2 million pairs of 2000 inputs, checked by ignored
+/// test `threshold_rate` (run it with `--release --ignored`).
+pub const CONFUSABLE_THRESHOLD: f64 = 12.0;
+
+/// Weight of the central circle
+const CENTER_WEIGHT: f64 = 2.0;
+
+/// Weight of the circles in the inner ring
+const INNER_RING_WEIGHT: f64 = 1.5;
+
+/// Weight of the circles in the outer ring
+const OUTER_RING_WEIGHT: f64 = 1.0;
+
+/// Weights of the circle positions, in [`crate::circles::position_circle_set`] order,
+/// normalized so that the sum is `1`
+///
+/// Central circle and the inner ring catch the eye first, and get larger
+/// weights than the outer ring.
+pub fn position_weights() -> [f64; 19] {
+    let mut weights = [0f64; 19];
+    for (i, weight) in weights.iter_mut().enumerate() {
+        *weight = match circle_ring(i) {
+            0 => CENTER_WEIGHT,
+            1 => INNER_RING_WEIGHT,
+            _ => OUTER_RING_WEIGHT,
+        };
+    }
+    let total: f64 = weights.iter().sum();
+    for weight in weights.iter_mut() {
+        *weight /= total;
+    }
+    weights
+}
+
+/// Color in CIE L\*a\*b\* color space, alpha is ignored
+fn to_lab(color: &Color) -> Lab<palette::white_point::D65, f64> {
+    Lab::from_color(Srgb::new(
+        color.red as f64 / 255f64,
+        color.green as f64 / 255f64,
+        color.blue as f64 / 255f64,
+    ))
+}
+
+/// CIEDE2000 color difference between two colors, alpha is ignored
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{similarity::color_difference, Color};
+///
+/// let red = Color{red: 255, green: 0, blue: 0, alpha: 255};
+/// let dark_red = Color{red: 250, green: 0, blue: 0, alpha: 255};
+/// assert_eq!(color_difference(&red, &red), 0.0);
+/// assert!(color_difference(&red, &dark_red) < 2.0);
+/// ```
+pub fn color_difference(first: &Color, second: &Color) -> f64 {
+    to_lab(first).get_color_difference(&to_lab(second))
+}
+
+/// Perceptual distance between two identicons, given by their colors
+///
+/// Weighted mean of CIEDE2000 differences of the colors at the same circle
+/// positions, with [`position_weights`].
+pub fn distance_with_colors(first: &[Color; 19], second: &[Color; 19]) -> f64 {
+    first
+        .iter()
+        .zip(second.iter())
+        .zip(position_weights().iter())
+        .map(|((a, b), weight)| color_difference(a, b) * weight)
+        .sum()
+}
+
+/// Perceptual distance between identicons of two `&[u8]` input slices
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::similarity::distance;
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let bob: &[u8] = &[142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97, 54, 147, 201, 18, 144, 156, 178, 38, 170, 71, 148, 242, 106, 72];
+/// assert_eq!(distance(alice, alice), 0.0);
+/// assert!(distance(alice, bob) > 20.0);
+/// ```
+pub fn distance(first: &[u8], second: &[u8]) -> f64 {
+    distance_with_colors(&get_colors(first), &get_colors(second))
+}
+
+/// Known identicon input the checked one could be confused with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Confusable {
+    /// Index of the input in the list of known inputs
+    pub index: usize,
+    /// Perceptual distance between the identicons
+    pub distance: f64,
+}
+
+/// Find the known input with identicon closest to the identicon of
+/// `into_id`, if it is closer than `threshold`
+///
+/// Known inputs equal to `into_id` are skipped: transfer to a known
+/// address is not a phishing attempt. [`CONFUSABLE_THRESHOLD`] is
+/// a reasonable threshold.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::similarity::{find_confusable, CONFUSABLE_THRESHOLD};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let bob: &[u8] = &[142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97, 54, 147, 201, 18, 144, 156, 178, 38, 170, 71, 148, 242, 106, 72];
+/// assert_eq!(find_confusable(alice, [alice, bob], CONFUSABLE_THRESHOLD), None);
+/// ```
+pub fn find_confusable<'a, I>(into_id: &[u8], known: I, threshold: f64) -> Option<Confusable>
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    let colors = get_colors(into_id);
+    known
+        .into_iter()
+        .enumerate()
+        .filter(|(_, known_id)| *known_id != into_id)
+        .map(|(index, known_id)| Confusable {
+            index,
+            distance: distance_with_colors(&colors, &get_colors(known_id)),
+        })
+        .filter(|confusable| confusable.distance < threshold)
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+/// Find the known identicon, given by colors, closest to the identicon
+/// with `colors`, if it is closer than `threshold`
+///
+/// Same as [`find_confusable`], for precalculated colors, for example from
+/// [`crate::colors::get_colors`] of the address book. Identical color sets
+/// are not skipped.
+pub fn find_confusable_with_colors<'a, I>(
+    colors: &[Color; 19],
+    known: I,
+    threshold: f64,
+) -> Option<Confusable>
+where
+    I: IntoIterator<Item = &'a [Color; 19]>,
+{
+    known
+        .into_iter()
+        .enumerate()
+        .map(|(index, known_colors)| Confusable {
+            index,
+            distance: distance_with_colors(colors, known_colors),
+        })
+        .filter(|confusable| confusable.distance < threshold)
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &[u8] = &[
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    #[test]
+    fn weights() {
+        let weights = position_weights();
+        assert!((weights.iter().sum::<f64>() - 1f64).abs() < 1e-12);
+        // central circle is the last one, inner ring circle is the second one
+        assert_eq!(weights[18], weights[0] * CENTER_WEIGHT);
+        assert_eq!(weights[1], weights[0] * INNER_RING_WEIGHT);
+    }
+
+    #[test]
+    fn difference_properties() {
+        let black = Color {
+            red: 0,
+            green: 0,
+            blue: 0,
+            alpha: 255,
+        };
+        let white = Color {
+            red: 255,
+            green: 255,
+            blue: 255,
+            alpha: 255,
+        };
+        // black and white differ only in lightness, by 100
+        let difference = color_difference(&black, &white);
+        assert!((difference - 100f64).abs() < 1e-3, "{}", difference);
+        assert_eq!(
+            color_difference(&white, &black),
+            color_difference(&black, &white)
+        );
+    }
+
+    #[test]
+    fn slightly_changed_colors_are_confusable() {
+        let colors = get_colors(ALICE);
+        let mut lookalike = colors;
+        for color in lookalike.iter_mut() {
+            color.red = color.red.saturating_add(6);
+            color.blue = color.blue.saturating_sub(6);
+        }
+        let bob_colors = get_colors(&[1, 2, 3]);
+        let found =
+            find_confusable_with_colors(&lookalike, [&bob_colors, &colors], CONFUSABLE_THRESHOLD)
+                .unwrap();
+        assert_eq!(found.index, 1);
+        assert!(found.distance > 0f64);
+    }
+
+    #[test]
+    fn random_inputs_are_not_confusable() {
+        let known: Vec<[u8; 4]> = (0u32..50).map(|a| a.to_le_bytes()).collect();
+        let close = (100u32..150)
+            .filter(|a| {
+                find_confusable(
+                    &a.to_le_bytes(),
+                    known.iter().map(|a| &a[..]),
+                    CONFUSABLE_THRESHOLD,
+                )
+                .is_some()
+            })
+            .count();
+        assert_eq!(close, 0);
+    }
+
+    /// Share of confusable pairs among identicons of random inputs
+    #[test]
+    #[ignore = "slow, about 2 million identicon pairs"]
+    fn threshold_rate() {
+        let colors: Vec<[Color; 19]> = (0u32..2000).map(|a| get_colors(&a.to_le_bytes())).collect();
+        let mut pairs = 0usize;
+        let mut close = 0usize;
+        for (i, first) in colors.iter().enumerate() {
+            for second in colors[i + 1..].iter() {
+                pairs += 1;
+                if distance_with_colors(first, second) < CONFUSABLE_THRESHOLD {
+                    close += 1;
+                }
+            }
+        }
+        assert!(
+            close * 100000 < pairs,
+            "{} of {} pairs are confusable",
+            close,
+            pairs
+        );
+    }
+}
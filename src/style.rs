@@ -0,0 +1,443 @@
+//! Colors of the identicon elements, that are not derived from the input
+//!
+//! Polkadot identicon is drawn over light grey disc, on transparent
+//! background. Small circles with palette byte `255` are transparent in
+//! javascript code, and show the disc underneath, i.e. are also light grey.
+//! [`Style`] sets these colors, for example for dark page themes.
+//!
+//! Style also sets optional decorations: outline of the disc, selection ring
+//! around the identicon, and strokes around the small circles. Decorations
+//! are drawn in both `png` and `svg` images, with the same anti-aliasing
+//! and centering as the circles. Decoration widths are set in parts of the
+//! image size, so that the identicons look the same at any size. Network
+//! badge (see [`crate::badge`], requires `alloc` feature) is drawn over
+//! everything else.
+//!
+//! Blockies, jdenticon and beachball generators with style (for example
+//! [`crate::generate_png_jdenticon_with_style`]) use only the background,
+//! the selection ring and the badge, the other settings are specific to the
+//! 19 circles.
+#[cfg(feature = "alloc")]
+use crate::badge::Badge;
+use crate::colors::{Color, ColorMode};
+
+/// Colors of the outer disc, background and blank small circles,
+/// decorations and network badge
+///
+/// Default style has no decorations and produces the same identicons as
+/// the generators with no style.
+///
+/// ## Example
+///
+/// Dark grey disc, with blank circles matching it, on opaque black background.
+///
+/// ```
+/// use plot_icon::{Color, Style};
+///
+/// let dark_grey = Color{red: 48, green: 48, blue: 48, alpha: 255};
+/// let style = Style {
+///     disc: dark_grey,
+///     background: Color{red: 0, green: 0, blue: 0, alpha: 255},
+///     blank: dark_grey,
+///     ..Style::default()
+/// };
+/// assert_ne!(style, Style::default());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Style {
+    /// Color of the outer disc enclosing the small circles,
+    /// [`Color::foreground`] by default
+    pub disc: Color,
+    /// Color of the image area outside of the disc,
+    /// [`Color::background`] (transparent) by default
+    ///
+    /// In `svg` images non-transparent background is drawn as a rectangle
+    /// under the disc.
+    pub background: Color,
+    /// Color of the small circles with palette byte `255`,
+    /// [`Color::foreground`] by default
+    ///
+    /// To keep such circles blending with the disc, set it to the disc color.
+    pub blank: Color,
+    /// Outline along the disc edge, inside the disc
+    pub outline: Option<Stroke>,
+    /// Selection ring along the image edge, identicon is shrunk to fit
+    /// within it
+    pub selection: Option<Selection>,
+    /// Stroke around each small circle, centered on the circle edge
+    pub circle_stroke: Option<Stroke>,
+    /// Network badge over the identicon
+    #[cfg(feature = "alloc")]
+    pub badge: Option<Badge>,
+    /// Conversion of the palette colors into RGB,
+    /// [`ColorMode::Palette`] by default
+    pub color_mode: ColorMode,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            disc: Color::foreground(),
+            background: Color::background(),
+            blank: Color::foreground(),
+            outline: None,
+            selection: None,
+            circle_stroke: None,
+            #[cfg(feature = "alloc")]
+            badge: None,
+            color_mode: ColorMode::Palette,
+        }
+    }
+}
+
+/// Stroke decoration
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stroke {
+    /// Stroke width, in parts of the image size
+    pub width: f32,
+    /// Stroke color
+    pub color: Color,
+}
+
+/// Selection ring, concentric with the identicon
+///
+/// ## Example
+///
+/// Selected identicon: ring is 4% of the image size, with transparent gap
+/// of the same width between the ring and the identicon.
+///
+/// ```
+/// use plot_icon::{style::Selection, Color, Style};
+///
+/// let style = Style {
+///     selection: Some(Selection {
+///         width: 0.04,
+///         gap: 0.04,
+///         color: Color{red: 230, green: 0, blue: 122, alpha: 255},
+///     }),
+///     ..Style::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Selection {
+    /// Ring width, in parts of the image size
+    pub width: f32,
+    /// Gap between the ring and the disc, in parts of the image size,
+    /// gap shows the background
+    pub gap: f32,
+    /// Ring color
+    pub color: Color,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors::{get_colors, get_colors_with_style};
+
+    #[cfg(any(feature = "pix", feature = "vec"))]
+    const ALICE: &[u8] = &[
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    /// Input with palette byte `255` among the identicon colors
+    const WITH_BLANK: &[u8] = &[0, 7];
+
+    const RED: Color = Color {
+        red: 255,
+        green: 0,
+        blue: 0,
+        alpha: 255,
+    };
+
+    #[cfg(any(feature = "pix", feature = "vec"))]
+    const BLACK: Color = Color {
+        red: 0,
+        green: 0,
+        blue: 0,
+        alpha: 255,
+    };
+
+    #[test]
+    fn blank_circles_recolored() {
+        let style = Style {
+            blank: RED,
+            ..Style::default()
+        };
+        let plain = get_colors(WITH_BLANK);
+        let styled = get_colors_with_style(WITH_BLANK, &style);
+        assert!(plain.contains(&Color::foreground()));
+        for (a, b) in plain.iter().zip(styled.iter()) {
+            if *a == Color::foreground() {
+                assert_eq!(*b, RED);
+            } else {
+                assert_eq!(a, b);
+            }
+        }
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn default_style_pixels_unchanged() {
+        use crate::circles;
+
+        for size in [16, 31, 64] {
+            let colors = get_colors(ALICE);
+            assert_eq!(
+                circles::calculate_png_data_with_style(size, colors, &Style::default()),
+                circles::calculate_png_data(size, colors)
+            );
+            assert_eq!(
+                circles::calculate_png_data_antialiased_with_style(size, colors, &Style::default()),
+                circles::calculate_png_data_antialiased(size, colors)
+            );
+        }
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn default_style_other_themes_pixels_unchanged() {
+        use crate::{beachball, blockies, jdenticon, make_png_from_data};
+
+        for size in [16u16, 31, 64] {
+            let png = |data: Vec<u8>| make_png_from_data(&data, size as u32).unwrap();
+            assert_eq!(
+                crate::generate_png_blockies_with_style(ALICE, size, &Style::default()).unwrap(),
+                png(blockies::calculate_png_data(
+                    size as u32,
+                    &blockies::get_blockies(ALICE)
+                ))
+            );
+            assert_eq!(
+                crate::generate_png_jdenticon_with_style(ALICE, size, &Style::default()).unwrap(),
+                png(jdenticon::calculate_png_data(&jdenticon::get_jdenticon(
+                    ALICE,
+                    size as u32
+                )))
+            );
+            assert_eq!(
+                crate::generate_png_beachball_with_style(ALICE, size, &Style::default()).unwrap(),
+                png(beachball::calculate_png_data(&beachball::get_beachball(
+                    ALICE,
+                    size as u32,
+                    beachball::ShapeKind::default()
+                )))
+            );
+        }
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn default_style_other_themes_svg_unchanged() {
+        use crate::{beachball, blockies, jdenticon};
+        use svg::Document;
+
+        let sized = |size: u16| {
+            Document::new()
+                .set("width", size)
+                .set("height", size)
+                .set("viewBox", (0, 0, size, size))
+        };
+        let mut expected = Document::new()
+            .set("viewBox", (0, 0, blockies::GRID_SIZE, blockies::GRID_SIZE))
+            .set("shape-rendering", "crispEdges");
+        for x in blockies::calculate_svg_data(&blockies::get_blockies(ALICE)) {
+            expected = expected.add(x);
+        }
+        assert_eq!(
+            crate::generate_svg_blockies_with_style(ALICE, &Style::default()).to_string(),
+            expected.to_string()
+        );
+        let mut expected = sized(64);
+        for x in jdenticon::calculate_svg_data(&jdenticon::get_jdenticon(ALICE, 64)) {
+            expected = expected.add(x);
+        }
+        assert_eq!(
+            crate::generate_svg_jdenticon_with_style(ALICE, 64, &Style::default()).to_string(),
+            expected.to_string()
+        );
+        let beachball = beachball::get_beachball(ALICE, 64, beachball::ShapeKind::default());
+        let mut expected = sized(64);
+        for x in beachball::calculate_svg_data(&beachball, "beachball-clip") {
+            expected = expected.add(x);
+        }
+        assert_eq!(
+            crate::generate_svg_beachball_with_style(ALICE, 64, &Style::default()).to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn other_themes_framed() {
+        let style = Style {
+            background: BLACK,
+            selection: Some(Selection {
+                width: 0.05,
+                gap: 0.05,
+                color: RED,
+            }),
+            ..Style::default()
+        };
+        let pixel = |content: &[u8], x: u32, y: u32| {
+            let image = image::load_from_memory(content).unwrap().to_rgba8();
+            image.get_pixel(x, y).0
+        };
+        for content in [
+            crate::generate_png_blockies_with_style(ALICE, 100, &style).unwrap(),
+            crate::generate_png_jdenticon_with_style(ALICE, 100, &style).unwrap(),
+            crate::generate_png_beachball_with_style(ALICE, 100, &style).unwrap(),
+        ] {
+            // background in the corner, ring is 5 pixels wide along the
+            // image edge, then 5 pixels gap
+            assert_eq!(pixel(&content, 0, 0), [0, 0, 0, 255]);
+            assert_eq!(pixel(&content, 2, 50), [255, 0, 0, 255]);
+            assert_eq!(pixel(&content, 7, 50), [0, 0, 0, 255]);
+            assert_eq!(pixel(&content, 92, 50), [0, 0, 0, 255]);
+        }
+        // blockies fill the whole square within the ring
+        let blockies = crate::generate_png_blockies_with_style(ALICE, 100, &style).unwrap();
+        assert_ne!(pixel(&blockies, 10, 10), [0, 0, 0, 255]);
+        assert_eq!(
+            pixel(&blockies, 10, 10),
+            pixel(&crate::generate_png_blockies(ALICE, 80).unwrap(), 0, 0)
+        );
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn other_themes_framed_svg() {
+        let style = Style {
+            background: BLACK,
+            selection: Some(Selection {
+                width: 0.05,
+                gap: 0.05,
+                color: RED,
+            }),
+            ..Style::default()
+        };
+        let document = crate::generate_svg_jdenticon_with_style(ALICE, 100, &style).to_string();
+        assert!(document
+            .contains("<rect fill=\"#000000\" height=\"100\" width=\"100\" x=\"0\" y=\"0\"/>"));
+        assert!(document.contains("<circle cx=\"50\" cy=\"50\" fill=\"none\" r=\"47.5\" stroke=\"#ff0000\" stroke-width=\"5\"/>"));
+        assert!(document.contains("<g transform=\"translate(10 10) scale(0.8)\">"));
+    }
+
+    #[cfg(all(feature = "pix", feature = "vec"))]
+    #[test]
+    fn other_themes_badge() {
+        use crate::badge::Badge;
+
+        let style = Style {
+            badge: Some(Badge::for_network(0)),
+            ..Style::default()
+        };
+        let plain = crate::generate_png_beachball(ALICE, 64).unwrap();
+        let badged = crate::generate_png_beachball_with_style(ALICE, 64, &style).unwrap();
+        assert_ne!(plain, badged);
+        let document = crate::generate_svg_blockies_with_style(ALICE, &style).to_string();
+        assert!(document.len() > crate::generate_svg_blockies(ALICE).to_string().len());
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn opaque_background() {
+        use crate::circles;
+
+        let style = Style {
+            disc: RED,
+            background: BLACK,
+            ..Style::default()
+        };
+        let colors = get_colors(ALICE);
+        for data in [
+            circles::calculate_png_data_with_style(32, colors, &style),
+            circles::calculate_png_data_antialiased_with_style(32, colors, &style),
+        ] {
+            // corner is background, and no pixel is transparent
+            assert_eq!(data[0..4], [0, 0, 0, 255]);
+            assert!(data.chunks_exact(4).all(|pixel| pixel[3] == 255));
+            // disc shows between small circles
+            assert!(data.chunks_exact(4).any(|pixel| pixel == [255, 0, 0, 255]));
+        }
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn decorations_in_pixels() {
+        use crate::circles;
+
+        let style = Style {
+            selection: Some(Selection {
+                width: 0.05,
+                gap: 0.05,
+                color: RED,
+            }),
+            outline: Some(Stroke {
+                width: 0.02,
+                color: BLACK,
+            }),
+            ..Style::default()
+        };
+        let colors = get_colors(ALICE);
+        let pixel = |data: &[u8], x: usize| {
+            let start = (50 * 100 + x) * 4;
+            data[start..start + 4].to_vec()
+        };
+        for data in [
+            circles::calculate_png_data_with_style(100, colors, &style),
+            circles::calculate_png_data_antialiased_with_style(100, colors, &style),
+        ] {
+            // ring is 5 pixels wide along the image edge, then 5 pixels gap,
+            // then 2 pixels of outline, inside the disc of radius 40
+            assert_eq!(pixel(&data, 2), [255, 0, 0, 255]);
+            assert_eq!(pixel(&data, 7)[3], 0);
+            assert_eq!(pixel(&data, 11), [0, 0, 0, 255]);
+            assert_eq!(pixel(&data, 13), Color::foreground().to_array());
+            // image stays symmetric
+            assert_eq!(pixel(&data, 2), pixel(&data, 97));
+            assert_eq!(pixel(&data, 11), pixel(&data, 88));
+        }
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn decorations_in_svg() {
+        let style = Style {
+            outline: Some(Stroke {
+                width: 0.0625,
+                color: BLACK,
+            }),
+            circle_stroke: Some(Stroke {
+                width: 0.01,
+                color: RED,
+            }),
+            ..Style::default()
+        };
+        let document = crate::generate_svg_with_style(ALICE, &style).to_string();
+        assert!(document.contains(
+            "<circle cx=\"0\" cy=\"0\" fill=\"none\" r=\"9.375\" stroke=\"#000000\" stroke-width=\"1.25\"/>"
+        ));
+        assert_eq!(document.matches("stroke=\"#ff0000\"").count(), 19);
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn svg_background() {
+        let translucent = Style {
+            background: Color { alpha: 51, ..BLACK },
+            ..Style::default()
+        };
+        let document = crate::generate_svg_with_style(ALICE, &translucent).to_string();
+        assert!(document.contains(
+            "<rect fill=\"#000000\" fill-opacity=\"0.2\" height=\"20\" width=\"20\" x=\"-10\" y=\"-10\"/>"
+        ));
+        assert_eq!(
+            crate::generate_svg_with_style(ALICE, &Style::default()).to_string(),
+            crate::generate_svg(ALICE).to_string()
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Identicon themes, selectable at runtime
+//!
+//! [`Theme`] turns identicon input bytes into [`Scene`], vector description
+//! of the identicon, and renders it into pixels or `svg` document. Themes
+//! follow the ones of polkadot.js `Identicon` component: `polkadot`
+//! (19 circles), `substrate` (jdenticon), `beachball`, `ethereum` (blockies)
+//! and `empty`. [`IconTheme`] selects one of them by name.
+//!
+//! Input for all themes is the public key (account bytes), same as
+//! polkadot.js gets it.
+use std::str::FromStr;
+
+#[cfg(feature = "vec")]
+use svg::Document;
+
+use crate::colors::get_colors_with_style;
+use crate::error::IdenticonError;
+use crate::scene::Scene;
+use crate::style::Style;
+use crate::{blockies, circles, jdenticon};
+
+#[cfg(feature = "ss58")]
+use crate::{beachball, input};
+
+/// Identicon theme: input bytes into identicon geometry
+///
+/// Only [`Theme::scene`] is required. By default the scene is rasterized
+/// with anti-aliasing, and written into `svg` element by element; themes
+/// with their own established rendering override this.
+pub trait Theme {
+    /// Calculate [`Scene`] for the input, for image `size_in_pixels` wide
+    ///
+    /// Some themes align shapes with the pixel grid, therefore the size is
+    /// needed for the geometry as well.
+    fn scene(&self, into_id: &[u8], size_in_pixels: u16) -> Scene;
+
+    /// Calculate RGBA8 pixel data (only pixel colors) for square image
+    /// `size_in_pixels` wide
+    #[cfg(feature = "pix")]
+    fn calculate_png_data(&self, into_id: &[u8], size_in_pixels: u16) -> Vec<u8> {
+        self.scene(into_id, size_in_pixels)
+            .calculate_png_data(size_in_pixels as u32)
+    }
+
+    /// Make [`svg::Document`](https://docs.rs/svg/latest/svg/type.Document.html)
+    #[cfg(feature = "vec")]
+    fn svg(&self, into_id: &[u8], size_in_pixels: u16) -> Document {
+        self.scene(into_id, size_in_pixels).to_svg("identicon-clip")
+    }
+
+    /// Check that the theme could be drawn in `png` and RGBA images, for
+    /// themes with user decorations that could not be rasterized
+    fn check_png(&self) -> Result<(), IdenticonError> {
+        Ok(())
+    }
+}
+
+/// Standard polkadot identicon: 19 circles within the outer circle
+///
+/// With default style, same images as [`crate::generate_png`] and
+/// [`crate::generate_svg`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polkadot {
+    /// Colors and decorations
+    pub style: Style,
+}
+
+impl Theme for Polkadot {
+    /// Scene with outer circle radius `10`, as in `svg` identicons
+    fn scene(&self, into_id: &[u8], _size_in_pixels: u16) -> Scene {
+        circles::calculate_scene_with_style(
+            10f32,
+            get_colors_with_style(into_id, &self.style),
+            &self.style,
+        )
+    }
+
+    #[cfg(feature = "pix")]
+    fn calculate_png_data(&self, into_id: &[u8], size_in_pixels: u16) -> Vec<u8> {
+        circles::calculate_png_data_with_style(
+            size_in_pixels as u32,
+            get_colors_with_style(into_id, &self.style),
+            &self.style,
+        )
+    }
+
+    #[cfg(feature = "vec")]
+    fn svg(&self, into_id: &[u8], _size_in_pixels: u16) -> Document {
+        crate::generate_svg_with_style(into_id, &self.style)
+    }
+
+    fn check_png(&self) -> Result<(), IdenticonError> {
+        match self.style.badge {
+            Some(ref badge) => badge.check_png(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Jdenticon, `substrate` theme of polkadot.js
+///
+/// Same images as [`crate::generate_png_jdenticon`] and
+/// [`crate::generate_svg_jdenticon`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Substrate;
+
+impl Theme for Substrate {
+    fn scene(&self, into_id: &[u8], size_in_pixels: u16) -> Scene {
+        jdenticon::calculate_scene(&jdenticon::get_jdenticon(into_id, size_in_pixels as u32))
+    }
+
+    #[cfg(feature = "pix")]
+    fn calculate_png_data(&self, into_id: &[u8], size_in_pixels: u16) -> Vec<u8> {
+        jdenticon::calculate_png_data(&jdenticon::get_jdenticon(into_id, size_in_pixels as u32))
+    }
+
+    #[cfg(feature = "vec")]
+    fn svg(&self, into_id: &[u8], size_in_pixels: u16) -> Document {
+        crate::generate_svg_jdenticon(into_id, size_in_pixels)
+    }
+}
+
+/// Beachball theme of polkadot.js
+///
+/// Polkadot.js seeds beachball with SS58 address string, so the input is
+/// encoded into the address with the SS58 network prefix first.
+#[cfg(feature = "ss58")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Beachball {
+    ss58_prefix: u16,
+}
+
+#[cfg(feature = "ss58")]
+impl Default for Beachball {
+    /// Generic substrate prefix `42`, polkadot.js default
+    fn default() -> Self {
+        Self { ss58_prefix: 42 }
+    }
+}
+
+#[cfg(feature = "ss58")]
+impl Beachball {
+    /// Beachball theme seeded with addresses of the SS58 network prefix;
+    /// error [`input::InputError::UnsupportedPrefix`] if the prefix could
+    /// not be encoded
+    pub fn new(ss58_prefix: u16) -> Result<Self, input::InputError> {
+        input::encode_ss58(&[], ss58_prefix)?;
+        Ok(Self { ss58_prefix })
+    }
+
+    /// SS58 network prefix of the address used as seed
+    pub fn ss58_prefix(&self) -> u16 {
+        self.ss58_prefix
+    }
+
+    /// Seed bytes for the input: SS58 address string
+    fn seed(&self, into_id: &[u8]) -> Vec<u8> {
+        input::encode_ss58(into_id, self.ss58_prefix)
+            .expect("prefix is checked on creation")
+            .into_bytes()
+    }
+
+    fn get_beachball(&self, into_id: &[u8], size_in_pixels: u16) -> beachball::Beachball {
+        beachball::get_beachball(
+            &self.seed(into_id),
+            size_in_pixels as u32,
+            beachball::ShapeKind::default(),
+        )
+    }
+}
+
+#[cfg(feature = "ss58")]
+impl Theme for Beachball {
+    fn scene(&self, into_id: &[u8], size_in_pixels: u16) -> Scene {
+        beachball::calculate_scene(&self.get_beachball(into_id, size_in_pixels))
+    }
+
+    #[cfg(feature = "pix")]
+    fn calculate_png_data(&self, into_id: &[u8], size_in_pixels: u16) -> Vec<u8> {
+        beachball::calculate_png_data(&self.get_beachball(into_id, size_in_pixels))
+    }
+
+    #[cfg(feature = "vec")]
+    fn svg(&self, into_id: &[u8], size_in_pixels: u16) -> Document {
+        crate::generate_svg_beachball(&self.seed(into_id), size_in_pixels)
+    }
+}
+
+/// Blockies, `ethereum` theme of polkadot.js, for 20-byte accounts
+///
+/// Same images as [`crate::generate_png_blockies`] and
+/// [`crate::generate_svg_blockies`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Ethereum;
+
+impl Theme for Ethereum {
+    fn scene(&self, into_id: &[u8], _size_in_pixels: u16) -> Scene {
+        blockies::calculate_scene(&blockies::get_blockies(into_id))
+    }
+
+    #[cfg(feature = "pix")]
+    fn calculate_png_data(&self, into_id: &[u8], size_in_pixels: u16) -> Vec<u8> {
+        blockies::calculate_png_data(size_in_pixels as u32, &blockies::get_blockies(into_id))
+    }
+
+    #[cfg(feature = "vec")]
+    fn svg(&self, into_id: &[u8], _size_in_pixels: u16) -> Document {
+        crate::generate_svg_blockies(into_id)
+    }
+}
+
+/// Placeholder with no identicon, input is ignored
+///
+/// Same as polkadot.js `empty` theme, that renders blank
+/// `<svg viewBox="0 0 64 64"/>`: scene has no elements, `png` images are
+/// fully transparent.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Empty;
+
+impl Theme for Empty {
+    fn scene(&self, _into_id: &[u8], _size_in_pixels: u16) -> Scene {
+        Scene {
+            view_box: (0f32, 0f32, 64f32, 64f32),
+            background: None,
+            clip_to_circle: false,
+            elements: Vec::new(),
+        }
+    }
+}
+
+/// Theme selected by name, as polkadot.js `Identicon` component does it
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::theme::{IconTheme, Theme};
+///
+/// let theme: IconTheme = "substrate".parse().unwrap();
+/// assert_eq!(theme, IconTheme::Substrate);
+/// assert_eq!(theme.to_string(), "substrate");
+/// assert!("jazzicon".parse::<IconTheme>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum IconTheme {
+    /// [`Polkadot`]
+    #[default]
+    Polkadot,
+    /// [`Substrate`]
+    Substrate,
+    /// [`Beachball`], with default SS58 prefix
+    #[cfg(feature = "ss58")]
+    Beachball,
+    /// [`Ethereum`]
+    Ethereum,
+    /// [`Empty`]
+    Empty,
+}
+
+impl IconTheme {
+    /// All available themes
+    pub const ALL: &'static [IconTheme] = &[
+        IconTheme::Polkadot,
+        IconTheme::Substrate,
+        #[cfg(feature = "ss58")]
+        IconTheme::Beachball,
+        IconTheme::Ethereum,
+        IconTheme::Empty,
+    ];
+
+    /// Theme name, as in polkadot.js
+    pub fn name(&self) -> &'static str {
+        match &self {
+            IconTheme::Polkadot => "polkadot",
+            IconTheme::Substrate => "substrate",
+            #[cfg(feature = "ss58")]
+            IconTheme::Beachball => "beachball",
+            IconTheme::Ethereum => "ethereum",
+            IconTheme::Empty => "empty",
+        }
+    }
+
+    /// Selected theme implementation
+    pub fn theme(&self) -> Box<dyn Theme> {
+        match &self {
+            IconTheme::Polkadot => Box::new(Polkadot::default()),
+            IconTheme::Substrate => Box::new(Substrate),
+            #[cfg(feature = "ss58")]
+            IconTheme::Beachball => Box::new(Beachball::default()),
+            IconTheme::Ethereum => Box::new(Ethereum),
+            IconTheme::Empty => Box::new(Empty),
+        }
+    }
+}
+
+impl Theme for IconTheme {
+    fn scene(&self, into_id: &[u8], size_in_pixels: u16) -> Scene {
+        self.theme().scene(into_id, size_in_pixels)
+    }
+
+    #[cfg(feature = "pix")]
+    fn calculate_png_data(&self, into_id: &[u8], size_in_pixels: u16) -> Vec<u8> {
+        self.theme().calculate_png_data(into_id, size_in_pixels)
+    }
+
+    #[cfg(feature = "vec")]
+    fn svg(&self, into_id: &[u8], size_in_pixels: u16) -> Document {
+        self.theme().svg(into_id, size_in_pixels)
+    }
+
+    fn check_png(&self) -> Result<(), IdenticonError> {
+        self.theme().check_png()
+    }
+}
+
+impl FromStr for IconTheme {
+    type Err = IdenticonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IconTheme::ALL
+            .iter()
+            .find(|theme| theme.name() == s)
+            .copied()
+            .ok_or_else(|| IdenticonError::UnknownTheme(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for IconTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors::get_colors;
+
+    const ALICE: &[u8] = &[
+        212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88,
+        133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+    ];
+
+    #[test]
+    fn names_round_trip() {
+        for theme in IconTheme::ALL.iter() {
+            assert_eq!(theme.to_string().parse::<IconTheme>().unwrap(), *theme);
+        }
+        assert!("Polkadot".parse::<IconTheme>().is_err());
+    }
+
+    #[test]
+    fn polkadot_scene() {
+        let scene = Polkadot::default().scene(ALICE, 30);
+        assert_eq!(scene.view_box, (-10f32, -10f32, 20f32, 20f32));
+        assert_eq!(scene.elements.len(), 20);
+        let colors = get_colors(ALICE);
+        for (element, color) in scene.elements[1..].iter().zip(colors.iter()) {
+            assert_eq!(element.color(), *color);
+        }
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn polkadot_pixels_unchanged() {
+        assert_eq!(
+            Polkadot::default().calculate_png_data(ALICE, 64),
+            circles::calculate_png_data(64, get_colors(ALICE))
+        );
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn polkadot_svg_unchanged() {
+        assert_eq!(
+            IconTheme::Polkadot.svg(ALICE, 64).to_string(),
+            crate::generate_svg(ALICE).to_string()
+        );
+    }
+
+    #[cfg(feature = "ss58")]
+    #[test]
+    fn beachball_prefix_checked() {
+        assert_eq!(Beachball::new(0).unwrap().ss58_prefix(), 0);
+        assert_eq!(Beachball::default().ss58_prefix(), 42);
+        assert!(Beachball::new(16384).is_err());
+    }
+
+    #[cfg(all(feature = "ss58", feature = "vec"))]
+    #[test]
+    fn beachball_seeded_with_address() {
+        let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        assert_eq!(
+            IconTheme::Beachball.svg(ALICE, 64).to_string(),
+            crate::generate_svg_beachball(address.as_bytes(), 64).to_string()
+        );
+    }
+
+    #[test]
+    fn empty_is_blank() {
+        let scene = Empty.scene(ALICE, 64);
+        assert_eq!(scene.view_box, (0f32, 0f32, 64f32, 64f32));
+        assert!(scene.elements.is_empty());
+        #[cfg(feature = "pix")]
+        assert!(Empty.calculate_png_data(ALICE, 16).iter().all(|a| *a == 0));
+    }
+
+    #[cfg(feature = "pix")]
+    #[test]
+    fn scene_rendering_close_to_theme_rendering() {
+        // default scene rasterization against the theme own one:
+        // same shapes, differences only on anti-aliased edges
+        let scene = Substrate.scene(ALICE, 32).calculate_png_data(32);
+        let own = Substrate.calculate_png_data(ALICE, 32);
+        let different = scene
+            .iter()
+            .zip(own.iter())
+            .filter(|(a, b)| (**a as i16 - **b as i16).abs() > 2)
+            .count();
+        assert!(different < own.len() / 100, "{} values differ", different);
+    }
+}
@@ -0,0 +1,378 @@
+//! Color vision deficiency simulation
+//!
+//! Identicons differing only in red-green hues could look the same for
+//! color-blind users. Here the colors are transformed as they are seen
+//! with complete dichromacy, and the differences between the circles and
+//! between the identicons are reported for each deficiency.
+//!
+//! Protanopia and deuteranopia are simulated with Viénot, Brettel and
+//! Mollon (1999) single-plane projection, tritanopia with Brettel, Viénot
+//! and Mollon (1997) two-plane projection, in linear RGB, with matrices
+//! as in [libDaltonLens](https://github.com/DaltonLens/libDaltonLens).
+//! Differences are CIEDE2000 differences, as in [`crate::similarity`].
+use crate::colors::Color;
+use crate::similarity::{color_difference, distance_with_colors};
+
+/// Viénot projection matrix for protanopia, in linear RGB
+const PROTANOPIA: [[f64; 3]; 3] = [
+    [0.11238, 0.88762, 0.00000],
+    [0.11238, 0.88762, -0.00000],
+    [0.00401, -0.00401, 1.00000],
+];
+
+/// Viénot projection matrix for deuteranopia, in linear RGB
+const DEUTERANOPIA: [[f64; 3]; 3] = [
+    [0.29031, 0.70969, -0.00000],
+    [0.29031, 0.70969, -0.00000],
+    [-0.02197, 0.02197, 1.00000],
+];
+
+/// Brettel projection matrices for tritanopia, in linear RGB, for the
+/// colors on the positive and on the negative side of separation plane
+const TRITANOPIA: [[[f64; 3]; 3]; 2] = [
+    [
+        [1.01277, 0.13548, -0.14826],
+        [-0.01243, 0.86812, 0.14431],
+        [0.07589, 0.80500, 0.11911],
+    ],
+    [
+        [0.93678, 0.18979, -0.12657],
+        [0.06154, 0.81526, 0.12320],
+        [-0.37562, 1.12767, 0.24796],
+    ],
+];
+
+/// Normal of the plane separating two tritanopia projections
+const TRITANOPIA_SEPARATION: [f64; 3] = [0.03901, -0.02788, -0.01113];
+
+/// Complete dichromacy type
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Deficiency {
+    /// No long-wavelength (red) cones
+    Protanopia,
+    /// No medium-wavelength (green) cones
+    Deuteranopia,
+    /// No short-wavelength (blue) cones
+    Tritanopia,
+}
+
+impl Deficiency {
+    /// All simulated deficiencies
+    pub const ALL: [Deficiency; 3] = [
+        Deficiency::Protanopia,
+        Deficiency::Deuteranopia,
+        Deficiency::Tritanopia,
+    ];
+
+    /// Projection matrix for the linear RGB color
+    fn matrix(&self, linear: [f64; 3]) -> &'static [[f64; 3]; 3] {
+        match &self {
+            Deficiency::Protanopia => &PROTANOPIA,
+            Deficiency::Deuteranopia => &DEUTERANOPIA,
+            Deficiency::Tritanopia => {
+                let side: f64 = linear
+                    .iter()
+                    .zip(TRITANOPIA_SEPARATION.iter())
+                    .map(|(a, b)| a * b)
+                    .sum();
+                if side >= 0f64 {
+                    &TRITANOPIA[0]
+                } else {
+                    &TRITANOPIA[1]
+                }
+            }
+        }
+    }
+}
+
+/// sRGB component into linear RGB
+fn to_linear(component: u8) -> f64 {
+    let value = component as f64 / 255f64;
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Linear RGB component into sRGB, clamped
+fn from_linear(value: f64) -> u8 {
+    let value = value.clamp(0f64, 1f64);
+    let encoded = if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1f64 / 2.4) - 0.055
+    };
+    (encoded * 255f64).round() as u8
+}
+
+/// Color as seen with given deficiency, alpha is kept
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{vision::{simulate, Deficiency}, Color};
+///
+/// let red = Color{red: 200, green: 40, blue: 40, alpha: 255};
+/// let green = Color{red: 120, green: 110, blue: 40, alpha: 255};
+/// let seen_red = simulate(&red, Deficiency::Deuteranopia);
+/// let seen_green = simulate(&green, Deficiency::Deuteranopia);
+/// assert!((seen_red.red as i16 - seen_green.red as i16).abs() < 10);
+/// ```
+pub fn simulate(color: &Color, deficiency: Deficiency) -> Color {
+    let linear = [
+        to_linear(color.red),
+        to_linear(color.green),
+        to_linear(color.blue),
+    ];
+    let matrix = deficiency.matrix(linear);
+    let project =
+        |row: &[f64; 3]| -> f64 { row.iter().zip(linear.iter()).map(|(a, b)| a * b).sum() };
+    Color {
+        red: from_linear(project(&matrix[0])),
+        green: from_linear(project(&matrix[1])),
+        blue: from_linear(project(&matrix[2])),
+        alpha: color.alpha,
+    }
+}
+
+/// Identicon colors as seen with given deficiency
+pub fn simulate_colors(colors: &[Color; 19], deficiency: Deficiency) -> [Color; 19] {
+    colors.map(|color| simulate(&color, deficiency))
+}
+
+/// Transform RGBA8 pixel data in place, as seen with given deficiency
+pub fn simulate_rgba(data: &mut [u8], deficiency: Deficiency) {
+    for pixel in data.chunks_exact_mut(4) {
+        let color = simulate(
+            &Color {
+                red: pixel[0],
+                green: pixel[1],
+                blue: pixel[2],
+                alpha: pixel[3],
+            },
+            deficiency,
+        );
+        pixel[..3].copy_from_slice(&[color.red, color.green, color.blue]);
+    }
+}
+
+/// Minimal difference between differently colored circles
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CircleDifference {
+    /// Indices of the closest circles
+    pub circles: (usize, usize),
+    /// CIEDE2000 difference of their colors
+    pub difference: f64,
+}
+
+/// Distinguishability of the identicon circles with given vision
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CircleReport {
+    /// Simulated deficiency, `None` for normal vision
+    pub deficiency: Option<Deficiency>,
+    /// Minimal difference between the circles that have different colors
+    /// with normal vision, `None` if all circles have the same color
+    pub min_difference: Option<CircleDifference>,
+}
+
+/// Distance between two identicons with given vision
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IdenticonReport {
+    /// Simulated deficiency, `None` for normal vision
+    pub deficiency: Option<Deficiency>,
+    /// Perceptual distance, see [`crate::similarity::distance_with_colors`]
+    pub distance: f64,
+}
+
+/// All visions to report: normal first, then all deficiencies
+fn visions() -> impl Iterator<Item = Option<Deficiency>> {
+    std::iter::once(None).chain(Deficiency::ALL.into_iter().map(Some))
+}
+
+fn seen_colors(colors: &[Color; 19], deficiency: Option<Deficiency>) -> [Color; 19] {
+    match deficiency {
+        Some(a) => simulate_colors(colors, a),
+        None => *colors,
+    }
+}
+
+/// Minimal difference between the circles, for normal vision and for each
+/// deficiency
+///
+/// Only the circles with different colors (with normal vision) are
+/// compared, same colored circles are same by coloring scheme design.
+///
+/// ## Example
+///
+/// ```
+/// use plot_icon::{colors::get_colors, vision::{circle_report, Deficiency}};
+///
+/// let alice: &[u8] = &[212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125];
+/// let report = circle_report(&get_colors(alice));
+/// assert_eq!(report.len(), 4);
+/// assert_eq!(report[0].deficiency, None);
+/// assert_eq!(report[2].deficiency, Some(Deficiency::Deuteranopia));
+/// ```
+pub fn circle_report(colors: &[Color; 19]) -> Vec<CircleReport> {
+    visions()
+        .map(|deficiency| {
+            let seen = seen_colors(colors, deficiency);
+            let mut min_difference: Option<CircleDifference> = None;
+            for i in 0..19 {
+                for j in i + 1..19 {
+                    if colors[i] == colors[j] {
+                        continue;
+                    }
+                    let difference = color_difference(&seen[i], &seen[j]);
+                    if min_difference.is_none_or(|a| difference < a.difference) {
+                        min_difference = Some(CircleDifference {
+                            circles: (i, j),
+                            difference,
+                        });
+                    }
+                }
+            }
+            CircleReport {
+                deficiency,
+                min_difference,
+            }
+        })
+        .collect()
+}
+
+/// Distance between two identicons, for normal vision and for each
+/// deficiency
+pub fn identicon_report(first: &[Color; 19], second: &[Color; 19]) -> Vec<IdenticonReport> {
+    visions()
+        .map(|deficiency| IdenticonReport {
+            deficiency,
+            distance: distance_with_colors(
+                &seen_colors(first, deficiency),
+                &seen_colors(second, deficiency),
+            ),
+        })
+        .collect()
+}
+
+/// Minimal difference between distinct opaque colors of RGBA8 pixel data,
+/// as seen with given deficiency (`None` for normal vision)
+///
+/// Intended for images with no anti-aliasing, such as [`crate::generate_rgba`],
+/// where each opaque color is the color of a circle; blended edge colors
+/// of anti-aliased images are close to the colors they are blended from.
+/// Returns `None` if there are less than two distinct opaque colors.
+pub fn min_difference_rgba(data: &[u8], deficiency: Option<Deficiency>) -> Option<f64> {
+    let mut colors: Vec<Color> = Vec::new();
+    for pixel in data.chunks_exact(4) {
+        let color = Color {
+            red: pixel[0],
+            green: pixel[1],
+            blue: pixel[2],
+            alpha: pixel[3],
+        };
+        if color.alpha == 255 && !colors.contains(&color) {
+            colors.push(color);
+        }
+    }
+    let seen: Vec<Color> = match deficiency {
+        Some(a) => colors.iter().map(|color| simulate(color, a)).collect(),
+        None => colors,
+    };
+    let mut min_difference: Option<f64> = None;
+    for i in 0..seen.len() {
+        for j in i + 1..seen.len() {
+            let difference = color_difference(&seen[i], &seen[j]);
+            if min_difference.is_none_or(|a| difference < a) {
+                min_difference = Some(difference);
+            }
+        }
+    }
+    min_difference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(red: u8, green: u8, blue: u8) -> Color {
+        Color {
+            red,
+            green,
+            blue,
+            alpha: 255,
+        }
+    }
+
+    #[test]
+    fn grays_are_unchanged() {
+        for value in [0u8, 4, 60, 128, 238, 255] {
+            for deficiency in Deficiency::ALL {
+                let seen = simulate(&rgb(value, value, value), deficiency);
+                for component in [seen.red, seen.green, seen.blue] {
+                    assert!(
+                        (component as i16 - value as i16).abs() <= 1,
+                        "{:?} {}: {:?}",
+                        deficiency,
+                        value,
+                        seen
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn red_green_confusion() {
+        let red = rgb(200, 60, 60);
+        let green = rgb(100, 140, 60);
+        let normal = color_difference(&red, &green);
+        for deficiency in [Deficiency::Protanopia, Deficiency::Deuteranopia] {
+            let seen = color_difference(&simulate(&red, deficiency), &simulate(&green, deficiency));
+            assert!(
+                seen < normal / 2f64,
+                "{:?}: {} vs {}",
+                deficiency,
+                seen,
+                normal
+            );
+        }
+        // blue-yellow axis is kept by protanopia and deuteranopia
+        let blue = rgb(40, 60, 200);
+        let yellow = rgb(220, 200, 40);
+        let seen = color_difference(
+            &simulate(&blue, Deficiency::Deuteranopia),
+            &simulate(&yellow, Deficiency::Deuteranopia),
+        );
+        assert!(seen > 50f64);
+    }
+
+    #[test]
+    fn reports() {
+        let colors = crate::colors::get_colors(&[0]);
+        let report = circle_report(&colors);
+        assert_eq!(report.len(), 4);
+        let normal = report[0].min_difference.unwrap();
+        assert_ne!(colors[normal.circles.0], colors[normal.circles.1]);
+        let same = identicon_report(&colors, &colors);
+        assert!(same.iter().all(|a| a.distance == 0f64));
+    }
+
+    #[test]
+    fn rgba_matches_colors() {
+        let red = rgb(200, 60, 60);
+        let green = rgb(100, 140, 60);
+        let mut data = vec![200, 60, 60, 255, 100, 140, 60, 255, 0, 0, 0, 0];
+        assert_eq!(
+            min_difference_rgba(&data, Some(Deficiency::Protanopia)),
+            Some(color_difference(
+                &simulate(&red, Deficiency::Protanopia),
+                &simulate(&green, Deficiency::Protanopia)
+            ))
+        );
+        simulate_rgba(&mut data, Deficiency::Protanopia);
+        let seen = simulate(&red, Deficiency::Protanopia);
+        assert_eq!(&data[..4], &[seen.red, seen.green, seen.blue, 255]);
+        assert_eq!(&data[8..], &[0, 0, 0, 0]);
+    }
+}